@@ -0,0 +1,154 @@
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error,
+    hivelocity::{HivelocityDeployer, HivelocityOutput, HivelocityPeriod},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HivelocityInvoiceItem {
+    pub description: Option<String>,
+    pub amount: Option<f64>,
+    pub date: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HivelocityCancellation {
+    /// Device was cancelled immediately, otherwise it stays active until the end of the billing term (None if not reported)
+    pub immediate: Option<bool>,
+    pub cancel_date: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HivelocityBilling {
+    pub service_id: Option<u64>,
+    pub period: Option<HivelocityPeriod>,
+    pub price: Option<f64>,
+    pub next_invoice_date: Option<String>,
+    pub upcoming_invoice_items: Vec<HivelocityInvoiceItem>,
+    pub cancellation: Option<HivelocityCancellation>,
+}
+
+impl HivelocityDeployer {
+    /// Current billing info, upcoming invoice items and cancellation status of a deployed device
+    pub async fn billing(&self, xnode: &HivelocityOutput) -> Result<HivelocityBilling, Error> {
+        let device_id = xnode.device_id;
+        let scope = self.scope();
+        let device = self
+            .client
+            .get(format!(
+                "https://core.hivelocity.net/api/v2/{scope}/{device_id}"
+            ))
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<serde_json::Value>()
+            .await
+            .map_err(Error::ReqwestError)?;
+        let service_id = device.get("serviceId").and_then(|id| id.as_u64());
+        let period = device
+            .get("period")
+            .and_then(|period| serde_json::from_value::<HivelocityPeriod>(period.clone()).ok());
+
+        let (price, next_invoice_date) = match service_id {
+            Some(service_id) => {
+                let service = self
+                    .client
+                    .get(format!(
+                        "https://core.hivelocity.net/api/v2/service/{service_id}"
+                    ))
+                    .header("X-API-KEY", self.api_key.expose())
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .map_err(Error::ReqwestError)?
+                    .json::<serde_json::Value>()
+                    .await
+                    .map_err(Error::ReqwestError)?;
+                (
+                    service.get("price").and_then(|price| price.as_f64()),
+                    service
+                        .get("nextInvoiceDate")
+                        .and_then(|date| date.as_str())
+                        .map(|date| date.to_string()),
+                )
+            }
+            None => (None, None),
+        };
+
+        let pending = self
+            .client
+            .get("https://core.hivelocity.net/api/v2/invoice/pending")
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<serde_json::Value>()
+            .await
+            .map_err(Error::ReqwestError)?;
+        let upcoming_invoice_items = match pending {
+            serde_json::Value::Array(items) => items
+                .iter()
+                .filter(|item| {
+                    item.get("deviceId").and_then(|id| id.as_u64()) == Some(device_id)
+                        || (service_id.is_some()
+                            && item.get("serviceId").and_then(|id| id.as_u64()) == service_id)
+                })
+                .map(|item| HivelocityInvoiceItem {
+                    description: item
+                        .get("description")
+                        .and_then(|description| description.as_str())
+                        .map(|description| description.to_string()),
+                    amount: item.get("amount").and_then(|amount| amount.as_f64()),
+                    date: item
+                        .get("date")
+                        .and_then(|date| date.as_str())
+                        .map(|date| date.to_string()),
+                })
+                .collect(),
+            _ => vec![],
+        };
+
+        let response = self
+            .client
+            .get(format!(
+                "https://core.hivelocity.net/api/v2/cancellation/device/{device_id}"
+            ))
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .map_err(Error::ReqwestError)?;
+        let cancellation = if response.status() == StatusCode::NOT_FOUND {
+            None
+        } else {
+            let cancellation = response
+                .error_for_status()
+                .map_err(Error::ReqwestError)?
+                .json::<serde_json::Value>()
+                .await
+                .map_err(Error::ReqwestError)?;
+            Some(HivelocityCancellation {
+                immediate: cancellation
+                    .get("immediate")
+                    .and_then(|immediate| immediate.as_bool()),
+                cancel_date: cancellation
+                    .get("cancelDate")
+                    .and_then(|date| date.as_str())
+                    .map(|date| date.to_string()),
+            })
+        };
+
+        Ok(HivelocityBilling {
+            service_id,
+            period,
+            price,
+            next_invoice_date,
+            upcoming_invoice_items,
+            cancellation,
+        })
+    }
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error,
+    hivelocity::{HivelocityDeployer, HivelocityHardware},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HivelocityOperatingSystem {
+    pub id: u64,
+    pub name: String,
+}
+
+impl HivelocityDeployer {
+    /// List operating systems available for the configured product (valid values for os_name)
+    pub async fn images(&self) -> Result<Vec<HivelocityOperatingSystem>, Error> {
+        let product_id = match &self.hardware {
+            HivelocityHardware::BareMetal { product_id, .. } => product_id,
+            HivelocityHardware::Compute { product_id, .. } => product_id,
+        };
+        self.client
+            .get(format!(
+                "https://core.hivelocity.net/api/v2/product/{product_id}/operating-systems"
+            ))
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<Vec<HivelocityOperatingSystem>>()
+            .await
+            .map_err(Error::ReqwestError)
+    }
+}
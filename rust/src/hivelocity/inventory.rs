@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error,
+    OptionalSupport::{self, NotSupported, Supported},
+    XnodeDeployerError,
+    hivelocity::{HivelocityDeployer, HivelocityError, HivelocityHardware, HivelocityPeriod},
+    utils::XnodeDeployerErrorInner,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HivelocityStock {
+    Available,
+    Limited,
+    Unavailable,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HivelocityPrice {
+    pub period: HivelocityPeriod,
+    pub price: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HivelocityProductAvailability {
+    pub location_name: String,
+    pub product_id: u64,
+    pub product_name: String,
+    pub stock: HivelocityStock,
+    pub prices: Vec<HivelocityPrice>,
+}
+
+impl HivelocityProductAvailability {
+    pub fn in_stock(&self) -> bool {
+        matches!(
+            self.stock,
+            HivelocityStock::Available | HivelocityStock::Limited
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InventoryProduct {
+    product_id: u64,
+    product_name: String,
+    stock: HivelocityStock,
+    hourly_pricing: Option<f64>,
+    monthly_pricing: Option<f64>,
+    quarterly_pricing: Option<f64>,
+    semi_annually_pricing: Option<f64>,
+    annually_pricing: Option<f64>,
+}
+
+impl HivelocityDeployer {
+    /// Stock and pricing of bare metal products per location
+    pub async fn availability(&self) -> Result<Vec<HivelocityProductAvailability>, Error> {
+        let response = self
+            .client
+            .get("https://core.hivelocity.net/api/v2/inventory/product")
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<HashMap<String, Vec<InventoryProduct>>>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        Ok(response
+            .into_iter()
+            .flat_map(|(location_name, products)| {
+                products
+                    .into_iter()
+                    .map(move |product| HivelocityProductAvailability {
+                        location_name: location_name.clone(),
+                        product_id: product.product_id,
+                        product_name: product.product_name,
+                        stock: product.stock,
+                        prices: [
+                            (HivelocityPeriod::Hourly, product.hourly_pricing),
+                            (HivelocityPeriod::Monthly, product.monthly_pricing),
+                            (HivelocityPeriod::Quarterly, product.quarterly_pricing),
+                            (
+                                HivelocityPeriod::SemiAnnually,
+                                product.semi_annually_pricing,
+                            ),
+                            (HivelocityPeriod::Annually, product.annually_pricing),
+                        ]
+                        .into_iter()
+                        .filter_map(|(period, price)| {
+                            price.map(|price| HivelocityPrice { period, price })
+                        })
+                        .collect(),
+                    })
+            })
+            .collect())
+    }
+
+    /// Price per billing period of the configured product and period, None if pricing is not available
+    ///
+    /// Only bare metal products are listed in the inventory, compute is NotSupported.
+    pub async fn estimate_cost(&self) -> Result<OptionalSupport<Option<HivelocityPrice>>, Error> {
+        let (location_name, product_id, period) = match &self.hardware {
+            HivelocityHardware::BareMetal {
+                location_name,
+                product_id,
+                period,
+                ..
+            } => (location_name, product_id, period),
+            HivelocityHardware::Compute { .. } => return Ok(NotSupported),
+        };
+
+        Ok(Supported(
+            self.availability()
+                .await?
+                .into_iter()
+                .find(|product| {
+                    product.location_name == *location_name && product.product_id == *product_id
+                })
+                .and_then(|product| {
+                    product
+                        .prices
+                        .into_iter()
+                        .find(|price| price.period == *period)
+                }),
+        ))
+    }
+
+    // Only bare metal products are listed in the inventory, compute is always considered in stock
+    pub(super) async fn check_stock(&self) -> Result<(), Error> {
+        if let HivelocityHardware::BareMetal {
+            location_name,
+            product_id,
+            ..
+        } = &self.hardware
+        {
+            let in_stock = self.availability().await?.iter().any(|product| {
+                product.location_name == *location_name
+                    && product.product_id == *product_id
+                    && product.in_stock()
+            });
+            if !in_stock {
+                return Err(Error::XnodeDeployerError(XnodeDeployerError::new(
+                    XnodeDeployerErrorInner::HivelocityError(HivelocityError::OutOfStock {
+                        location_name: location_name.clone(),
+                        product_id: *product_id,
+                    }),
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
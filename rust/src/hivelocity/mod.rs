@@ -4,10 +4,29 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+mod billing;
+mod images;
+mod inventory;
+mod network;
+mod power;
+mod reinstall;
+mod ssh_keys;
+mod status;
+mod tags;
+mod vlan;
+pub use billing::*;
+pub use images::*;
+pub use inventory::*;
+pub use network::*;
+pub use ssh_keys::*;
+pub use status::*;
+pub use tags::*;
+pub use vlan::*;
+
 use crate::{
     DeployInput, Error,
-    OptionalSupport::{self, Supported},
-    XnodeDeployer, XnodeDeployerError,
+    OptionalSupport::{self, NotSupported, Supported},
+    Secret, SshConfig, XnodeDeployer, XnodeDeployerError,
     utils::XnodeDeployerErrorInner,
 };
 
@@ -22,6 +41,10 @@ pub enum HivelocityError {
     ResponseInvalidDeviceId {
         device_id: serde_json::Value,
     },
+    OutOfStock {
+        location_name: String,
+        product_id: u64,
+    },
 }
 
 impl Display for HivelocityError {
@@ -37,6 +60,12 @@ impl Display for HivelocityError {
                 HivelocityError::ResponseInvalidDeviceId { device_id } => {
                     format!("Hivelocity response invalid device id: {device_id}")
                 }
+                HivelocityError::OutOfStock {
+                    location_name,
+                    product_id,
+                } => {
+                    format!("Hivelocity product {product_id} out of stock in {location_name}")
+                }
             }
             .as_str(),
         )
@@ -46,16 +75,41 @@ impl Display for HivelocityError {
 #[derive(Debug, Clone)]
 pub struct HivelocityDeployer {
     client: Client,
-    api_key: String,
+    api_key: Secret,
     hardware: HivelocityHardware,
+    ssh: Option<SshConfig>,
+    stock_check: bool,
 }
 
 impl HivelocityDeployer {
     pub fn new(api_key: String, hardware: HivelocityHardware) -> Self {
         Self {
             client: Client::new(),
-            api_key,
+            api_key: Secret::new(api_key),
             hardware,
+            ssh: None,
+            stock_check: false,
+        }
+    }
+
+    /// Enable SSH access to deployed hardware, used for operations not supported by the provider API
+    pub fn with_ssh(mut self, ssh: SshConfig) -> Self {
+        self.ssh = Some(ssh);
+        self
+    }
+
+    /// Verify the product is in stock before ordering, failing with OutOfStock instead
+    ///
+    /// Only applies to BareMetal hardware, compute is not listed in the inventory so it is never checked.
+    pub fn with_stock_check(mut self) -> Self {
+        self.stock_check = true;
+        self
+    }
+
+    fn scope(&self) -> &'static str {
+        match self.hardware {
+            HivelocityHardware::BareMetal { .. } => "bare-metal-devices",
+            HivelocityHardware::Compute { .. } => "compute",
         }
     }
 }
@@ -64,6 +118,10 @@ impl XnodeDeployer for HivelocityDeployer {
     type ProviderOutput = HivelocityOutput;
 
     async fn deploy(&self, input: DeployInput) -> Result<Self::ProviderOutput, Error> {
+        input.validate()?;
+        if self.stock_check {
+            self.check_stock().await?;
+        }
         log::info!(
             "Hivelocity deployment of {input:?} on {hardware:?} started",
             hardware = self.hardware
@@ -75,6 +133,8 @@ impl XnodeDeployer for HivelocityDeployer {
                 tags,
                 product_id,
                 hostname,
+                os_name,
+                public_ssh_key_id,
             } => self
                 .client
                 .post("https://core.hivelocity.net/api/v2/bare-metal-devices/")
@@ -84,8 +144,9 @@ impl XnodeDeployer for HivelocityDeployer {
                     "tags": tags,
                     "script": input.cloud_init(),
                     "productId": product_id,
-                    "osName": "Ubuntu 24.04",
-                    "hostname": hostname
+                    "osName": os_name,
+                    "hostname": hostname,
+                    "publicSshKeyId": public_ssh_key_id
                 })),
             HivelocityHardware::Compute {
                 location_name,
@@ -93,6 +154,8 @@ impl XnodeDeployer for HivelocityDeployer {
                 tags,
                 product_id,
                 hostname,
+                os_name,
+                public_ssh_key_id,
             } => self
                 .client
                 .post("https://core.hivelocity.net/api/v2/compute/")
@@ -102,11 +165,12 @@ impl XnodeDeployer for HivelocityDeployer {
                     "tags": tags,
                     "script": input.cloud_init(),
                     "productId": product_id,
-                    "osName": "Ubuntu 24.04 (VPS)",
-                    "hostname": hostname
+                    "osName": os_name,
+                    "hostname": hostname,
+                    "publicSshKeyId": public_ssh_key_id
                 })),
         }
-        .header("X-API-KEY", self.api_key.clone())
+        .header("X-API-KEY", self.api_key.expose())
         .send()
         .await
         .and_then(|response| response.error_for_status())
@@ -155,15 +219,12 @@ impl XnodeDeployer for HivelocityDeployer {
     async fn undeploy(&self, xnode: Self::ProviderOutput) -> Result<(), Error> {
         let device_id = xnode.device_id;
         log::info!("Undeploying hivelocity device {device_id} started");
-        let scope = match self.hardware {
-            HivelocityHardware::BareMetal { .. } => "bare-metal-devices",
-            HivelocityHardware::Compute { .. } => "compute",
-        };
+        let scope = self.scope();
         self.client
             .delete(format!(
                 "https://core.hivelocity.net/api/v2/{scope}/{device_id}"
             ))
-            .header("X-API-KEY", self.api_key.clone())
+            .header("X-API-KEY", self.api_key.expose())
             .send()
             .await
             .and_then(|response| response.error_for_status())
@@ -178,16 +239,13 @@ impl XnodeDeployer for HivelocityDeployer {
         xnode: &Self::ProviderOutput,
     ) -> Result<OptionalSupport<Option<Ipv4Addr>>, Error> {
         let device_id = xnode.device_id;
-        let scope = match self.hardware {
-            HivelocityHardware::BareMetal { .. } => "bare-metal-devices",
-            HivelocityHardware::Compute { .. } => "compute",
-        };
+        let scope = self.scope();
         let response = self
             .client
             .get(format!(
                 "https://core.hivelocity.net/api/v2/{scope}/{device_id}"
             ))
-            .header("X-API-KEY", self.api_key.clone())
+            .header("X-API-KEY", self.api_key.expose())
             .send()
             .await
             .and_then(|response| response.error_for_status())
@@ -206,6 +264,24 @@ impl XnodeDeployer for HivelocityDeployer {
 
         Ok(Supported(None))
     }
+
+    async fn install_log(
+        &self,
+        xnode: &Self::ProviderOutput,
+    ) -> Result<OptionalSupport<Option<String>>, Error> {
+        // Hivelocity has no console log API, only available over SSH
+        let ssh = match &self.ssh {
+            Some(ssh) => ssh,
+            None => return Ok(NotSupported),
+        };
+
+        let ip = match self.ipv4(xnode).await? {
+            Supported(Some(ip)) => ip,
+            _ => return Ok(Supported(None)),
+        };
+
+        ssh.install_log(ip).await.map(|log| Supported(Some(log)))
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -218,21 +294,47 @@ pub enum HivelocityHardware {
     // https://developers.hivelocity.net/reference/post_bare_metal_device_resource
     BareMetal {
         location_name: String,
-        period: String,
+        period: HivelocityPeriod,
         tags: Option<Vec<String>>,
         product_id: u64,
         hostname: String,
+        #[serde(default = "default_bare_metal_os_name")]
+        os_name: String,
+        #[serde(default)]
+        public_ssh_key_id: Option<u64>,
     },
     // https://developers.hivelocity.net/reference/post_compute_resource
     Compute {
         location_name: String,
-        period: String,
+        period: HivelocityPeriod,
         tags: Option<Vec<String>>,
         product_id: u64,
         hostname: String,
+        #[serde(default = "default_compute_os_name")]
+        os_name: String,
+        #[serde(default)]
+        public_ssh_key_id: Option<u64>,
     },
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum HivelocityPeriod {
+    Hourly,
+    Monthly,
+    Quarterly,
+    SemiAnnually,
+    Annually,
+}
+
+fn default_bare_metal_os_name() -> String {
+    "Ubuntu 24.04".to_string()
+}
+
+fn default_compute_os_name() -> String {
+    "Ubuntu 24.04 (VPS)".to_string()
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum HivelocityUndeployInput {
     BareMetal { device_id: u64 },
@@ -0,0 +1,92 @@
+use std::net::{IpAddr, Ipv4Addr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error,
+    OptionalSupport::Supported,
+    XnodeDeployer,
+    hivelocity::{HivelocityDeployer, HivelocityOutput},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HivelocityIpAssignment {
+    pub subnet: String,
+    pub gateway: Option<IpAddr>,
+    pub netmask: Option<String>,
+    pub usable_ips: Vec<IpAddr>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HivelocityNetworkInfo {
+    pub primary_ip: Option<Ipv4Addr>,
+    pub ipv4: Vec<HivelocityIpAssignment>,
+    pub ipv6: Vec<HivelocityIpAssignment>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IpAssignmentResponse {
+    subnet: String,
+    gateway: Option<String>,
+    netmask: Option<String>,
+    usable_ips: Option<Vec<String>>,
+}
+
+impl HivelocityDeployer {
+    /// All ipv4 and ipv6 assignments of a deployed device
+    pub async fn network_info(
+        &self,
+        xnode: &HivelocityOutput,
+    ) -> Result<HivelocityNetworkInfo, Error> {
+        let device_id = xnode.device_id;
+        let response = self
+            .client
+            .get(format!(
+                "https://core.hivelocity.net/api/v2/device/{device_id}/ips"
+            ))
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<Vec<IpAssignmentResponse>>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        let mut ipv4 = vec![];
+        let mut ipv6 = vec![];
+        for assignment in response {
+            let is_ipv6 = assignment.subnet.contains(':');
+            let assignment = HivelocityIpAssignment {
+                subnet: assignment.subnet,
+                gateway: assignment
+                    .gateway
+                    .and_then(|gateway| gateway.parse::<IpAddr>().ok()),
+                netmask: assignment.netmask,
+                usable_ips: assignment
+                    .usable_ips
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|ip| ip.parse::<IpAddr>().ok())
+                    .collect(),
+            };
+            if is_ipv6 {
+                ipv6.push(assignment);
+            } else {
+                ipv4.push(assignment);
+            }
+        }
+
+        let primary_ip = match self.ipv4(xnode).await? {
+            Supported(ip) => ip,
+            _ => None,
+        };
+
+        Ok(HivelocityNetworkInfo {
+            primary_ip,
+            ipv4,
+            ipv6,
+        })
+    }
+}
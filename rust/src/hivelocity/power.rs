@@ -0,0 +1,71 @@
+use crate::{
+    Error, PowerState, XnodePower,
+    hivelocity::{HivelocityDeployer, HivelocityOutput},
+};
+
+impl HivelocityDeployer {
+    async fn power_action(&self, xnode: &HivelocityOutput, action: &str) -> Result<(), Error> {
+        let device_id = xnode.device_id;
+        log::info!("Hivelocity power {action} of device {device_id} started");
+        self.client
+            .post(format!(
+                "https://core.hivelocity.net/api/v2/device/{device_id}/power"
+            ))
+            .query(&[("action", action)])
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?;
+
+        log::info!("Hivelocity power {action} of device {device_id} succeeded");
+        Ok(())
+    }
+}
+
+// Power state from the powerStatus field of a device or power response
+pub(super) fn power_state(response: &serde_json::Value) -> PowerState {
+    match response.get("powerStatus") {
+        Some(serde_json::Value::String(power_status)) => {
+            match power_status.to_uppercase().as_str() {
+                "ON" => PowerState::On,
+                "OFF" => PowerState::Off,
+                _ => PowerState::Unknown,
+            }
+        }
+        _ => PowerState::Unknown,
+    }
+}
+
+impl XnodePower for HivelocityDeployer {
+    async fn power_state(&self, xnode: &Self::ProviderOutput) -> Result<PowerState, Error> {
+        let device_id = xnode.device_id;
+        let response = self
+            .client
+            .get(format!(
+                "https://core.hivelocity.net/api/v2/device/{device_id}/power"
+            ))
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<serde_json::Value>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        Ok(power_state(&response))
+    }
+
+    async fn power_on(&self, xnode: &Self::ProviderOutput) -> Result<(), Error> {
+        self.power_action(xnode, "boot").await
+    }
+
+    async fn power_off(&self, xnode: &Self::ProviderOutput) -> Result<(), Error> {
+        self.power_action(xnode, "shutdown").await
+    }
+
+    async fn reboot(&self, xnode: &Self::ProviderOutput) -> Result<(), Error> {
+        self.power_action(xnode, "reboot").await
+    }
+}
@@ -0,0 +1,60 @@
+use serde_json::json;
+
+use crate::{
+    DeployInput, Error,
+    hivelocity::{HivelocityDeployer, HivelocityHardware, HivelocityOutput},
+};
+
+impl HivelocityDeployer {
+    /// Reload the operating system with a fresh XnodeOS install, keeping the device (and its contract and ip)
+    pub async fn reinstall(
+        &self,
+        xnode: &HivelocityOutput,
+        input: DeployInput,
+    ) -> Result<(), Error> {
+        input.validate()?;
+        let device_id = xnode.device_id;
+        log::info!(
+            "Hivelocity reinstall of device {device_id} with {input:?} on {hardware:?} started",
+            hardware = self.hardware
+        );
+        let (tags, hostname, os_name, public_ssh_key_id) = match &self.hardware {
+            HivelocityHardware::BareMetal {
+                tags,
+                hostname,
+                os_name,
+                public_ssh_key_id,
+                ..
+            } => (tags, hostname, os_name, public_ssh_key_id),
+            HivelocityHardware::Compute {
+                tags,
+                hostname,
+                os_name,
+                public_ssh_key_id,
+                ..
+            } => (tags, hostname, os_name, public_ssh_key_id),
+        };
+        // Only a change of operating system reloads the device, forceReload also reloads the current one
+        let scope = self.scope();
+        self.client
+            .put(format!(
+                "https://core.hivelocity.net/api/v2/{scope}/{device_id}"
+            ))
+            .json(&json!({
+                "tags": tags,
+                "script": input.cloud_init(),
+                "osName": os_name,
+                "hostname": hostname,
+                "publicSshKeyId": public_ssh_key_id,
+                "forceReload": true
+            }))
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?;
+
+        log::info!("Hivelocity reinstall of device {device_id} succeeded");
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{Error, hivelocity::HivelocityDeployer};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HivelocitySshKey {
+    pub ssh_key_id: u64,
+    pub name: String,
+    pub public_key: String,
+}
+
+impl HivelocityDeployer {
+    /// Store a public SSH key, its id can be used as public_ssh_key_id
+    pub async fn create_ssh_key(
+        &self,
+        name: String,
+        public_key: String,
+    ) -> Result<HivelocitySshKey, Error> {
+        log::info!("Hivelocity creating ssh key {name} started");
+        let ssh_key = self
+            .client
+            .post("https://core.hivelocity.net/api/v2/ssh_key/")
+            .json(&json!({
+                "name": name,
+                "publicKey": public_key
+            }))
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<HivelocitySshKey>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        log::info!("Hivelocity creating ssh key succeeded: {ssh_key:?}");
+        Ok(ssh_key)
+    }
+
+    pub async fn ssh_keys(&self) -> Result<Vec<HivelocitySshKey>, Error> {
+        self.client
+            .get("https://core.hivelocity.net/api/v2/ssh_key/")
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<Vec<HivelocitySshKey>>()
+            .await
+            .map_err(Error::ReqwestError)
+    }
+
+    pub async fn delete_ssh_key(&self, ssh_key_id: u64) -> Result<(), Error> {
+        log::info!("Hivelocity deleting ssh key {ssh_key_id} started");
+        self.client
+            .delete(format!(
+                "https://core.hivelocity.net/api/v2/ssh_key/{ssh_key_id}"
+            ))
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?;
+
+        log::info!("Hivelocity deleting ssh key {ssh_key_id} succeeded");
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error, PowerState,
+    hivelocity::{HivelocityDeployer, HivelocityOutput, power::power_state},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum HivelocityProvisioningState {
+    /// Order placed, awaiting processing by Hivelocity
+    Ordered,
+    /// Hardware is being built
+    Provisioning,
+    /// Operating system (and XnodeOS script) is being installed
+    OsInstall,
+    /// Device is powered on and reachable
+    Active,
+    /// Device is provisioned, but powered off
+    Off,
+    /// Order was cancelled or declined
+    Failed,
+}
+
+impl HivelocityDeployer {
+    /// Current provisioning state of a deployed device, bare metal builds can take 10+ minutes
+    pub async fn provisioning_state(
+        &self,
+        xnode: &HivelocityOutput,
+    ) -> Result<HivelocityProvisioningState, Error> {
+        let device_id = xnode.device_id;
+        let scope = self.scope();
+        let device = self
+            .client
+            .get(format!(
+                "https://core.hivelocity.net/api/v2/{scope}/{device_id}"
+            ))
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<serde_json::Value>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        let order_id = match device.get("orderId") {
+            Some(serde_json::Value::Number(order_id)) => order_id.as_u64(),
+            Some(serde_json::Value::String(order_id)) => order_id.parse::<u64>().ok(),
+            _ => None,
+        };
+        if let Some(order_id) = order_id {
+            let order = self
+                .client
+                .get(format!(
+                    "https://core.hivelocity.net/api/v2/order/{order_id}"
+                ))
+                .header("X-API-KEY", self.api_key.expose())
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(Error::ReqwestError)?
+                .json::<serde_json::Value>()
+                .await
+                .map_err(Error::ReqwestError)?;
+
+            if let Some(serde_json::Value::String(status)) = order.get("status") {
+                match status.to_lowercase().as_str() {
+                    "complete" | "completed" => {}
+                    "cancelled" | "canceled" | "declined" | "fraud" | "failed" => {
+                        return Ok(HivelocityProvisioningState::Failed);
+                    }
+                    _ => return Ok(HivelocityProvisioningState::Ordered),
+                }
+            }
+        }
+
+        if let Some(serde_json::Value::Bool(true)) = device.get("isReload") {
+            return Ok(HivelocityProvisioningState::OsInstall);
+        }
+
+        let has_ip = match device.get("primaryIp") {
+            Some(serde_json::Value::String(ip)) => !ip.is_empty(),
+            _ => false,
+        };
+        Ok(match power_state(&device) {
+            PowerState::On if has_ip => HivelocityProvisioningState::Active,
+            PowerState::Off if has_ip => HivelocityProvisioningState::Off,
+            _ => HivelocityProvisioningState::Provisioning,
+        })
+    }
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    Error,
+    hivelocity::{HivelocityDeployer, HivelocityOutput},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum HivelocityDeviceKind {
+    BareMetal,
+    Compute,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HivelocityDevice {
+    pub device_id: u64,
+    pub kind: HivelocityDeviceKind,
+    pub hostname: Option<String>,
+    pub location_name: Option<String>,
+    pub primary_ip: Option<String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeviceResponse {
+    device_id: u64,
+    hostname: Option<String>,
+    location_name: Option<String>,
+    primary_ip: Option<String>,
+    tags: Option<Vec<String>>,
+}
+
+impl HivelocityDeployer {
+    /// List all bare metal and compute devices of the account, including devices not deployed by this deployer
+    pub async fn devices(&self) -> Result<Vec<HivelocityDevice>, Error> {
+        let mut devices = vec![];
+        for (scope, kind) in [
+            ("bare-metal-devices", HivelocityDeviceKind::BareMetal),
+            ("compute", HivelocityDeviceKind::Compute),
+        ] {
+            let response = self
+                .client
+                .get(format!("https://core.hivelocity.net/api/v2/{scope}/"))
+                .header("X-API-KEY", self.api_key.expose())
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(Error::ReqwestError)?
+                .json::<Vec<DeviceResponse>>()
+                .await
+                .map_err(Error::ReqwestError)?;
+
+            devices.extend(response.into_iter().map(|device| HivelocityDevice {
+                device_id: device.device_id,
+                kind: kind.clone(),
+                hostname: device.hostname,
+                location_name: device.location_name,
+                primary_ip: device.primary_ip,
+                tags: device.tags.unwrap_or_default(),
+            }));
+        }
+
+        Ok(devices)
+    }
+
+    /// List all devices that have the given tag
+    pub async fn devices_with_tag(&self, tag: &str) -> Result<Vec<HivelocityDevice>, Error> {
+        Ok(self
+            .devices()
+            .await?
+            .into_iter()
+            .filter(|device| device.tags.iter().any(|device_tag| device_tag == tag))
+            .collect())
+    }
+
+    /// Replace the tags of a deployed device
+    pub async fn set_tags(&self, xnode: &HivelocityOutput, tags: Vec<String>) -> Result<(), Error> {
+        let device_id = xnode.device_id;
+        log::info!("Hivelocity updating tags of device {device_id} to {tags:?} started");
+        self.client
+            .put(format!(
+                "https://core.hivelocity.net/api/v2/device/{device_id}/tags"
+            ))
+            .json(&json!({
+                "tags": tags
+            }))
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?;
+
+        log::info!("Hivelocity updating tags of device {device_id} succeeded");
+        Ok(())
+    }
+}
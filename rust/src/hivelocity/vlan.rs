@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    Error,
+    hivelocity::{HivelocityDeployer, HivelocityOutput},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HivelocityVlan {
+    pub vlan_id: u64,
+    pub facility_code: String,
+    #[serde(rename = "type")]
+    pub vlan_type: String,
+    #[serde(default)]
+    pub port_ids: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HivelocityPort {
+    pub port_id: u64,
+    pub name: Option<String>,
+}
+
+impl HivelocityDeployer {
+    /// Create a private VLAN in a location (for example TPA1)
+    pub async fn create_vlan(&self, location_name: String) -> Result<HivelocityVlan, Error> {
+        log::info!("Hivelocity creating private vlan in {location_name} started");
+        let vlan = self
+            .client
+            .post("https://core.hivelocity.net/api/v2/vlan/")
+            .json(&json!({
+                "facilityCode": location_name,
+                "type": "private"
+            }))
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<HivelocityVlan>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        log::info!("Hivelocity creating private vlan succeeded: {vlan:?}");
+        Ok(vlan)
+    }
+
+    pub async fn vlans(&self) -> Result<Vec<HivelocityVlan>, Error> {
+        self.client
+            .get("https://core.hivelocity.net/api/v2/vlan/")
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<Vec<HivelocityVlan>>()
+            .await
+            .map_err(Error::ReqwestError)
+    }
+
+    pub async fn vlan(&self, vlan_id: u64) -> Result<HivelocityVlan, Error> {
+        self.client
+            .get(format!("https://core.hivelocity.net/api/v2/vlan/{vlan_id}"))
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<HivelocityVlan>()
+            .await
+            .map_err(Error::ReqwestError)
+    }
+
+    /// Network ports of a deployed device
+    pub async fn ports(&self, xnode: &HivelocityOutput) -> Result<Vec<HivelocityPort>, Error> {
+        let device_id = xnode.device_id;
+        self.client
+            .get(format!(
+                "https://core.hivelocity.net/api/v2/device/{device_id}/ports"
+            ))
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<Vec<HivelocityPort>>()
+            .await
+            .map_err(Error::ReqwestError)
+    }
+
+    /// Devices (by device id) with at least one port in the VLAN
+    pub async fn vlan_members(&self, vlan_id: u64) -> Result<Vec<u64>, Error> {
+        let vlan = self.vlan(vlan_id).await?;
+        let mut members = vec![];
+        // A VLAN only spans a single facility, skip devices in other locations
+        for device in
+            self.devices().await?.into_iter().filter(|device| {
+                device.location_name.as_deref() == Some(vlan.facility_code.as_str())
+            })
+        {
+            let ports = self
+                .ports(&HivelocityOutput {
+                    device_id: device.device_id,
+                })
+                .await?;
+            if ports
+                .iter()
+                .any(|port| vlan.port_ids.contains(&port.port_id))
+            {
+                members.push(device.device_id);
+            }
+        }
+
+        Ok(members)
+    }
+
+    /// Add a single port (the private port of a device, see ports) to the VLAN
+    ///
+    /// Adding the public uplink port to a private VLAN disconnects the device from the internet.
+    pub async fn attach_vlan(&self, vlan_id: u64, port_id: u64) -> Result<(), Error> {
+        log::info!("Hivelocity attaching port {port_id} to vlan {vlan_id} started");
+        let mut port_ids = self.vlan(vlan_id).await?.port_ids;
+        if !port_ids.contains(&port_id) {
+            port_ids.push(port_id);
+            self.set_vlan_ports(vlan_id, port_ids).await?;
+        }
+
+        log::info!("Hivelocity attaching port {port_id} to vlan {vlan_id} succeeded");
+        Ok(())
+    }
+
+    /// Remove a single port from the VLAN
+    pub async fn detach_vlan(&self, vlan_id: u64, port_id: u64) -> Result<(), Error> {
+        log::info!("Hivelocity detaching port {port_id} from vlan {vlan_id} started");
+        let port_ids = self.vlan(vlan_id).await?.port_ids;
+        if port_ids.contains(&port_id) {
+            self.set_vlan_ports(
+                vlan_id,
+                port_ids.into_iter().filter(|id| *id != port_id).collect(),
+            )
+            .await?;
+        }
+
+        log::info!("Hivelocity detaching port {port_id} from vlan {vlan_id} succeeded");
+        Ok(())
+    }
+
+    async fn set_vlan_ports(&self, vlan_id: u64, port_ids: Vec<u64>) -> Result<(), Error> {
+        self.client
+            .put(format!("https://core.hivelocity.net/api/v2/vlan/{vlan_id}"))
+            .json(&json!({
+                "portIds": port_ids
+            }))
+            .header("X-API-KEY", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?;
+
+        Ok(())
+    }
+}
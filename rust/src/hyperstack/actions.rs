@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error, PowerState, XnodeDeployerError, XnodePower,
+    hyperstack::{HyperstackDeployer, HyperstackError, HyperstackOutput, HyperstackStatus},
+    utils::XnodeDeployerErrorInner,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum HyperstackAction {
+    Start,
+    Stop,
+    Reboot,
+    HardReboot,
+}
+
+impl HyperstackAction {
+    fn path(&self) -> &'static str {
+        match self {
+            HyperstackAction::Start => "start",
+            HyperstackAction::Stop => "stop",
+            HyperstackAction::Reboot => "reboot",
+            HyperstackAction::HardReboot => "hard-reboot",
+        }
+    }
+
+    /// Whether the action can be performed on a virtual machine with this status
+    pub fn allowed(&self, status: &HyperstackStatus) -> bool {
+        match self {
+            HyperstackAction::Start => matches!(status, HyperstackStatus::Shutoff),
+            HyperstackAction::Stop | HyperstackAction::Reboot => {
+                matches!(status, HyperstackStatus::Active)
+            }
+            HyperstackAction::HardReboot => matches!(
+                status,
+                HyperstackStatus::Active | HyperstackStatus::Shutoff | HyperstackStatus::Error
+            ),
+        }
+    }
+}
+
+impl HyperstackDeployer {
+    /// Perform an action on a deployed virtual machine, fails with InvalidStatusForAction if its status does not allow it
+    pub async fn action(
+        &self,
+        xnode: &HyperstackOutput,
+        action: HyperstackAction,
+    ) -> Result<(), Error> {
+        let id = xnode.id;
+        let status = self.status(xnode).await?;
+        if !action.allowed(&status) {
+            return Err(Error::XnodeDeployerError(XnodeDeployerError::new(
+                XnodeDeployerErrorInner::HyperstackError(HyperstackError::InvalidStatusForAction {
+                    action,
+                    status,
+                }),
+            )));
+        }
+
+        log::info!("Hyperstack {action:?} of virtual machine {id} started");
+        let path = action.path();
+        self.client
+            .get(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}/{path}"
+            ))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?;
+
+        log::info!("Hyperstack {action:?} of virtual machine {id} succeeded");
+        Ok(())
+    }
+}
+
+impl XnodePower for HyperstackDeployer {
+    async fn power_state(&self, xnode: &Self::ProviderOutput) -> Result<PowerState, Error> {
+        Ok(match self.status(xnode).await? {
+            HyperstackStatus::Active => PowerState::On,
+            HyperstackStatus::Shutoff | HyperstackStatus::Hibernated => PowerState::Off,
+            _ => PowerState::Unknown,
+        })
+    }
+
+    async fn power_on(&self, xnode: &Self::ProviderOutput) -> Result<(), Error> {
+        self.action(xnode, HyperstackAction::Start).await
+    }
+
+    async fn power_off(&self, xnode: &Self::ProviderOutput) -> Result<(), Error> {
+        self.action(xnode, HyperstackAction::Stop).await
+    }
+
+    async fn reboot(&self, xnode: &Self::ProviderOutput) -> Result<(), Error> {
+        self.action(xnode, HyperstackAction::HardReboot).await
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{
+    DeployInput, Error, XnodeDeployerError,
+    hyperstack::{HyperstackDeployer, HyperstackError, HyperstackHardware, HyperstackOutput},
+    utils::XnodeDeployerErrorInner,
+};
+
+impl HyperstackDeployer {
+    /// Provision count virtual machines with XnodeOS in a single request
+    ///
+    /// In case not all virtual machines were created, PartialBatchDeploy contains the ones that were (to undeploy or keep).
+    /// Volumes can only be attached to a single deployment, so volume_ids must be empty.
+    pub async fn deploy_batch(
+        &self,
+        input: DeployInput,
+        count: u32,
+    ) -> Result<Vec<HyperstackOutput>, Error> {
+        if count == 0 {
+            return Err(Error::XnodeDeployerError(XnodeDeployerError::new(
+                XnodeDeployerErrorInner::HyperstackError(HyperstackError::InvalidBatchCount {
+                    count,
+                }),
+            )));
+        }
+        match &self.hardware {
+            HyperstackHardware::VirtualMachine { volume_ids, .. } => {
+                if !volume_ids.is_empty() {
+                    return Err(Error::XnodeDeployerError(XnodeDeployerError::new(
+                        XnodeDeployerErrorInner::HyperstackError(
+                            HyperstackError::BatchVolumesUnsupported {
+                                volume_ids: volume_ids.clone(),
+                            },
+                        ),
+                    )));
+                }
+            }
+        }
+        input.validate()?;
+        log::info!(
+            "Hyperstack batch deployment of {count} instances of {input:?} on {hardware:?} started",
+            hardware = self.hardware
+        );
+        let deployed = self.deploy_virtual_machines(&input, count).await?;
+
+        log::info!("Hyperstack batch deployment succeeded: {deployed:?}");
+        Ok(deployed)
+    }
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, hyperstack::HyperstackDeployer};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HyperstackEnvironment {
+    pub id: u64,
+    pub name: String,
+    pub region: String,
+}
+
+#[derive(Deserialize)]
+struct EnvironmentsResponse {
+    environments: Vec<HyperstackEnvironment>,
+}
+
+impl HyperstackDeployer {
+    /// List environments of the account (valid values for environment_name)
+    pub async fn environments(&self) -> Result<Vec<HyperstackEnvironment>, Error> {
+        let response = self
+            .client
+            .get("https://infrahub-api.nexgencloud.com/v1/core/environments")
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<EnvironmentsResponse>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        Ok(response.environments)
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, hyperstack::HyperstackDeployer};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HyperstackFlavor {
+    pub id: u64,
+    pub name: String,
+    pub region_name: String,
+    /// GPU model, empty for CPU only flavors
+    pub gpu: String,
+    pub gpu_count: u32,
+    pub cpu: u32,
+    /// RAM in GB
+    pub ram: f64,
+    /// Disk in GB
+    pub disk: Option<u64>,
+    pub stock_available: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct FlavorsResponse {
+    data: Vec<FlavorGroup>,
+}
+
+#[derive(Deserialize)]
+struct FlavorGroup {
+    flavors: Vec<HyperstackFlavor>,
+}
+
+impl HyperstackDeployer {
+    /// List flavors (valid values for flavor_name), optionally limited to a region
+    pub async fn flavors(&self, region: Option<String>) -> Result<Vec<HyperstackFlavor>, Error> {
+        let mut request = self
+            .client
+            .get("https://infrahub-api.nexgencloud.com/v1/core/flavors");
+        if let Some(region) = region {
+            request = request.query(&[("region", region)]);
+        }
+        let response = request
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<FlavorsResponse>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        Ok(response
+            .data
+            .into_iter()
+            .flat_map(|group| group.flavors)
+            .collect())
+    }
+}
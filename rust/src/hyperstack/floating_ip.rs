@@ -0,0 +1,46 @@
+use crate::{
+    Error,
+    hyperstack::{HyperstackDeployer, HyperstackOutput},
+};
+
+impl HyperstackDeployer {
+    /// Attach a floating ip (public ipv4) to a deployed virtual machine, the address is allocated by Hyperstack
+    ///
+    /// A specific (previously detached) address cannot be requested, so DNS has to be updated to the new address.
+    pub async fn attach_floating_ip(&self, xnode: &HyperstackOutput) -> Result<(), Error> {
+        let id = xnode.id;
+        log::info!("Hyperstack attaching floating ip to virtual machine {id} started");
+        self.client
+            .post(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}/attach-floatingip"
+            ))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?;
+
+        log::info!("Hyperstack attaching floating ip to virtual machine {id} succeeded");
+        Ok(())
+    }
+
+    /// Detach the floating ip of a deployed virtual machine
+    ///
+    /// The address is released, attaching a floating ip afterwards (to this or another virtual machine) allocates a new one.
+    pub async fn detach_floating_ip(&self, xnode: &HyperstackOutput) -> Result<(), Error> {
+        let id = xnode.id;
+        log::info!("Hyperstack detaching floating ip from virtual machine {id} started");
+        self.client
+            .post(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}/detach-floatingip"
+            ))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?;
+
+        log::info!("Hyperstack detaching floating ip from virtual machine {id} succeeded");
+        Ok(())
+    }
+}
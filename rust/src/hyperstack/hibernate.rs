@@ -0,0 +1,42 @@
+use crate::{
+    Error,
+    hyperstack::{HyperstackDeployer, HyperstackOutput},
+};
+
+impl HyperstackDeployer {
+    /// Hibernate a deployed virtual machine, releasing its GPU (and stopping GPU billing) while keeping its data
+    pub async fn hibernate(&self, xnode: &HyperstackOutput) -> Result<(), Error> {
+        let id = xnode.id;
+        log::info!("Hyperstack hibernating virtual machine {id} started");
+        self.client
+            .get(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}/hibernate"
+            ))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?;
+
+        log::info!("Hyperstack hibernating virtual machine {id} succeeded");
+        Ok(())
+    }
+
+    /// Restore a hibernated virtual machine
+    pub async fn restore(&self, xnode: &HyperstackOutput) -> Result<(), Error> {
+        let id = xnode.id;
+        log::info!("Hyperstack restoring virtual machine {id} started");
+        self.client
+            .get(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}/hibernate-restore"
+            ))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?;
+
+        log::info!("Hyperstack restoring virtual machine {id} succeeded");
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, hyperstack::HyperstackDeployer};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HyperstackImage {
+    pub id: u64,
+    pub name: String,
+    pub region_name: String,
+    #[serde(rename = "type")]
+    pub image_type: Option<String>,
+    pub version: Option<String>,
+    pub size: Option<u64>,
+    pub is_public: Option<bool>,
+    /// For example ACTIVE once the image can be deployed
+    pub status: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ImagesResponse {
+    images: Vec<ImageGroup>,
+}
+
+#[derive(Deserialize)]
+struct ImageGroup {
+    images: Vec<HyperstackImage>,
+}
+
+impl HyperstackDeployer {
+    /// List images available to the account (valid values for image_name), optionally limited to a region
+    pub async fn images(&self, region: Option<String>) -> Result<Vec<HyperstackImage>, Error> {
+        let mut request = self
+            .client
+            .get("https://infrahub-api.nexgencloud.com/v1/core/images");
+        if let Some(region) = region {
+            request = request.query(&[("region", region)]);
+        }
+        let response = request
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<ImagesResponse>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        Ok(response
+            .images
+            .into_iter()
+            .flat_map(|group| group.images)
+            .collect())
+    }
+}
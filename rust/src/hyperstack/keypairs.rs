@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{Error, hyperstack::HyperstackDeployer};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HyperstackKeypair {
+    pub id: u64,
+    pub name: String,
+    pub environment_name: String,
+    pub public_key: String,
+    pub fingerprint: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct KeypairsResponse {
+    keypairs: Vec<KeypairResponse>,
+}
+
+#[derive(Deserialize)]
+struct ImportKeypairResponse {
+    keypair: KeypairResponse,
+}
+
+#[derive(Deserialize)]
+struct KeypairResponse {
+    id: u64,
+    name: String,
+    environment: KeypairEnvironment,
+    public_key: String,
+    fingerprint: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct KeypairEnvironment {
+    name: String,
+}
+
+impl From<KeypairResponse> for HyperstackKeypair {
+    fn from(keypair: KeypairResponse) -> Self {
+        Self {
+            id: keypair.id,
+            name: keypair.name,
+            environment_name: keypair.environment.name,
+            public_key: keypair.public_key,
+            fingerprint: keypair.fingerprint,
+        }
+    }
+}
+
+impl HyperstackDeployer {
+    /// List keypairs of the account (valid values for key_name)
+    pub async fn keypairs(&self) -> Result<Vec<HyperstackKeypair>, Error> {
+        let response = self
+            .client
+            .get("https://infrahub-api.nexgencloud.com/v1/core/keypairs")
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<KeypairsResponse>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        Ok(response
+            .keypairs
+            .into_iter()
+            .map(HyperstackKeypair::from)
+            .collect())
+    }
+
+    /// Import a public key as keypair into an environment
+    pub async fn import_keypair(
+        &self,
+        name: String,
+        environment_name: String,
+        public_key: String,
+    ) -> Result<HyperstackKeypair, Error> {
+        log::info!("Hyperstack importing keypair {name} into {environment_name} started");
+        let response = self
+            .client
+            .post("https://infrahub-api.nexgencloud.com/v1/core/keypairs")
+            .json(&json!({
+                "name": name,
+                "environment_name": environment_name,
+                "public_key": public_key
+            }))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<ImportKeypairResponse>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        let keypair = HyperstackKeypair::from(response.keypair);
+        log::info!("Hyperstack importing keypair succeeded: {keypair:?}");
+        Ok(keypair)
+    }
+
+    pub async fn delete_keypair(&self, id: u64) -> Result<(), Error> {
+        log::info!("Hyperstack deleting keypair {id} started");
+        self.client
+            .delete(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/keypairs/{id}"
+            ))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?;
+
+        log::info!("Hyperstack deleting keypair {id} succeeded");
+        Ok(())
+    }
+
+    // Name of a keypair with this public key in the environment, importing it as key_name if none exists yet
+    pub(super) async fn ensure_keypair(
+        &self,
+        environment_name: &str,
+        key_name: &str,
+        public_key: &str,
+    ) -> Result<String, Error> {
+        if let Some(keypair) = self.keypairs().await?.into_iter().find(|keypair| {
+            keypair.environment_name == environment_name
+                && keypair.public_key.trim() == public_key.trim()
+        }) {
+            return Ok(keypair.name);
+        }
+
+        self.import_keypair(
+            key_name.to_string(),
+            environment_name.to_string(),
+            public_key.to_string(),
+        )
+        .await
+        .map(|keypair| keypair.name)
+    }
+}
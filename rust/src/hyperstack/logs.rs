@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::{
+    Error,
+    hyperstack::{HyperstackDeployer, HyperstackOutput},
+};
+
+// Console output lines to request, enough to cover a full XnodeOS install
+const INSTALL_LOG_LINE_COUNT: u64 = 1000;
+// Console logs are collected asynchronously after requesting them
+const LOGS_POLL_INTERVAL: Duration = Duration::from_secs(3);
+const LOGS_POLL_ATTEMPTS: u32 = 10;
+
+impl HyperstackDeployer {
+    // Request the console output of a virtual machine and wait until it has been collected
+    pub(super) async fn console_logs(
+        &self,
+        xnode: &HyperstackOutput,
+    ) -> Result<Option<String>, Error> {
+        let id = xnode.id;
+        let response = self
+            .client
+            .post(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}/logs"
+            ))
+            .header("api_key", self.api_key.expose())
+            .json(&json!({
+                "line_count": INSTALL_LOG_LINE_COUNT
+            }))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<serde_json::Value>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        let request_id = match response.get("request_id") {
+            Some(serde_json::Value::Number(request_id)) => request_id.to_string(),
+            _ => return Ok(None),
+        };
+
+        for _ in 0..LOGS_POLL_ATTEMPTS {
+            tokio::time::sleep(LOGS_POLL_INTERVAL).await;
+            let response = self
+                .client
+                .get(format!(
+                    "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}/logs"
+                ))
+                .query(&[("request_id", &request_id)])
+                .header("api_key", self.api_key.expose())
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(Error::ReqwestError)?
+                .json::<serde_json::Value>()
+                .await
+                .map_err(Error::ReqwestError)?;
+
+            if let Some(serde_json::Value::String(logs)) = response.get("logs")
+                && !logs.is_empty()
+            {
+                return Ok(Some(logs.clone()));
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -4,10 +4,35 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
+mod actions;
+mod batch;
+mod environments;
+mod flavors;
+mod floating_ip;
+mod hibernate;
+mod images;
+mod keypairs;
+mod logs;
+mod pricing;
+mod security;
+mod snapshots;
+mod status;
+mod volumes;
+pub use actions::*;
+pub use environments::*;
+pub use flavors::*;
+pub use images::*;
+pub use keypairs::*;
+pub use pricing::*;
+pub use security::*;
+pub use snapshots::*;
+pub use status::*;
+pub use volumes::*;
+
 use crate::{
     DeployInput, Error,
     OptionalSupport::{self, Supported},
-    XnodeDeployer, XnodeDeployerError,
+    Secret, SshConfig, XnodeDeployer, XnodeDeployerError,
     utils::XnodeDeployerErrorInner,
 };
 
@@ -29,6 +54,41 @@ pub enum HyperstackError {
     ResponseInvalidId {
         id: serde_json::Value,
     },
+    ResponseMissingStatus {
+        response: serde_json::Value,
+    },
+    PartialBatchDeploy {
+        requested: u32,
+        deployed: Vec<HyperstackOutput>,
+        failures: Vec<serde_json::Value>,
+    },
+    InvalidBatchCount {
+        count: u32,
+    },
+    BatchVolumesUnsupported {
+        volume_ids: Vec<u64>,
+    },
+    InvalidStatusForAction {
+        action: HyperstackAction,
+        status: HyperstackStatus,
+    },
+    VolumeAttachFailed {
+        xnode: HyperstackOutput,
+        volume_ids: Vec<u64>,
+        error: Option<Box<Error>>,
+    },
+    EnvironmentNotFound {
+        name: String,
+    },
+    ImageRegionMismatch {
+        name: String,
+        image_region: String,
+        region: String,
+    },
+    ImageNotReady {
+        name: String,
+        status: Option<String>,
+    },
 }
 
 impl Display for HyperstackError {
@@ -53,6 +113,55 @@ impl Display for HyperstackError {
                 HyperstackError::ResponseInvalidId { id } => {
                     format!("Hyperstack response invalid id: {id}")
                 }
+                HyperstackError::ResponseMissingStatus { response } => {
+                    format!("Hyperstack response missing status: {response}")
+                }
+                HyperstackError::PartialBatchDeploy {
+                    requested,
+                    deployed,
+                    failures,
+                } => {
+                    format!(
+                        "Hyperstack batch deploy partially failed: {deployed} of {requested} deployed, failures: {failures:?}",
+                        deployed = deployed.len()
+                    )
+                }
+                HyperstackError::InvalidBatchCount { count } => {
+                    format!("Hyperstack batch deploy invalid count: {count}")
+                }
+                HyperstackError::BatchVolumesUnsupported { volume_ids } => {
+                    format!("Hyperstack batch deploy cannot attach volumes {volume_ids:?}")
+                }
+                HyperstackError::InvalidStatusForAction { action, status } => {
+                    format!("Hyperstack action {action:?} not allowed with status {status:?}")
+                }
+                HyperstackError::VolumeAttachFailed {
+                    xnode,
+                    volume_ids,
+                    error,
+                } => match error {
+                    Some(error) => format!(
+                        "Hyperstack attaching volumes {volume_ids:?} to virtual machine {id} failed: {error:?}",
+                        id = xnode.id
+                    ),
+                    None => format!(
+                        "Hyperstack virtual machine {id} did not become active to attach volumes {volume_ids:?}",
+                        id = xnode.id
+                    ),
+                },
+                HyperstackError::EnvironmentNotFound { name } => {
+                    format!("Hyperstack environment {name} not found")
+                }
+                HyperstackError::ImageRegionMismatch {
+                    name,
+                    image_region,
+                    region,
+                } => {
+                    format!("Hyperstack image {name} is in region {image_region} instead of {region}")
+                }
+                HyperstackError::ImageNotReady { name, status } => {
+                    format!("Hyperstack image {name} did not become ready (status {status:?})")
+                }
             }
             .as_str(),
         )
@@ -62,140 +171,180 @@ impl Display for HyperstackError {
 #[derive(Debug, Clone)]
 pub struct HyperstackDeployer {
     client: Client,
-    api_key: String,
+    api_key: Secret,
     hardware: HyperstackHardware,
+    ssh: Option<SshConfig>,
 }
 
 impl HyperstackDeployer {
     pub fn new(api_key: String, hardware: HyperstackHardware) -> Self {
         Self {
             client: Client::new(),
-            api_key,
+            api_key: Secret::new(api_key),
             hardware,
+            ssh: None,
         }
     }
-}
 
-impl XnodeDeployer for HyperstackDeployer {
-    type ProviderOutput = HyperstackOutput;
+    /// Enable SSH access to deployed hardware, used for operations not supported by the provider API
+    pub fn with_ssh(mut self, ssh: SshConfig) -> Self {
+        self.ssh = Some(ssh);
+        self
+    }
 
-    async fn deploy(&self, input: DeployInput) -> Result<Self::ProviderOutput, Error> {
-        log::info!(
-            "Hyperstack deployment of {input:?} on {hardware:?} started",
-            hardware = self.hardware
-        );
-        let response = match &self.hardware {
+    // Create count virtual machines with the configured hardware, returning the raw response
+    async fn create_virtual_machines(
+        &self,
+        input: &DeployInput,
+        count: u32,
+    ) -> Result<serde_json::Value, Error> {
+        match &self.hardware {
             HyperstackHardware::VirtualMachine {
                 name,
                 environment_name,
                 flavor_name,
                 key_name,
-            } => self
-                .client
-                .post("https://infrahub-api.nexgencloud.com/v1/core/virtual-machines")
-                .json(&json!({
-                    "name": name,
-                    "environment_name": environment_name,
-                    "image_name": "Ubuntu Server 22.04 LTS (Jammy Jellyfish)",
-                    "flavor_name": flavor_name,
-                    "key_name": key_name,
-                    "count": 1,
-                    "assign_floating_ip": true,
-                    "user_data": input.cloud_init(),
-                    "security_rules": [
-                        {
-                            "direction": "ingress",
-                            "protocol": "tcp",
-                            "ethertype": "IPv4",
-                            "remote_ip_prefix": "0.0.0.0/0",
-                            "port_range_min": 1,
-                            "port_range_max": 65535
-                        },
-                        {
-                            "direction": "ingress",
-                            "protocol": "udp",
-                            "ethertype": "IPv4",
-                            "remote_ip_prefix": "0.0.0.0/0",
-                            "port_range_min": 1,
-                            "port_range_max": 65535
-                        }
-                    ]
-                })),
+                image_name,
+                security_rules,
+                public_key,
+                assign_floating_ip,
+                snapshot_id,
+                ..
+            } => {
+                let image_name = match snapshot_id {
+                    Some(snapshot_id) => {
+                        self.ensure_snapshot_image(*snapshot_id, environment_name)
+                            .await?
+                    }
+                    None => image_name.clone(),
+                };
+                let key_name = match public_key {
+                    Some(public_key) => {
+                        self.ensure_keypair(environment_name, key_name, public_key)
+                            .await?
+                    }
+                    None => key_name.clone(),
+                };
+                self.client
+                    .post("https://infrahub-api.nexgencloud.com/v1/core/virtual-machines")
+                    .json(&json!({
+                        "name": name,
+                        "environment_name": environment_name,
+                        "image_name": image_name,
+                        "flavor_name": flavor_name,
+                        "key_name": key_name,
+                        "count": count,
+                        "assign_floating_ip": assign_floating_ip,
+                        "user_data": input.cloud_init(),
+                        "security_rules": security_rules
+                    }))
+            }
         }
-        .header("api_key", self.api_key.clone())
+        .header("api_key", self.api_key.expose())
         .send()
         .await
         .and_then(|response| response.error_for_status())
         .map_err(Error::ReqwestError)?
         .json::<serde_json::Value>()
         .await
-        .map_err(Error::ReqwestError)?;
+        .map_err(Error::ReqwestError)
+    }
 
-        let id = match &response {
-            serde_json::Value::Object(map) => map
-                .get("instances")
-                .ok_or(Error::XnodeDeployerError(XnodeDeployerError::new(
-                    XnodeDeployerErrorInner::HyperstackError(
-                        HyperstackError::ResponseMissingInstances { map: map.clone() },
-                    ),
-                )))
-                .and_then(|instances| match instances {
-                    serde_json::Value::Array(array) => {
-                        array
-                            .first()
-                            .ok_or(Error::XnodeDeployerError(XnodeDeployerError::new(
-                                XnodeDeployerErrorInner::HyperstackError(
-                                    HyperstackError::ResponseEmptyInstances {},
-                                ),
-                            )))
-                    }
-                    _ => Err(Error::XnodeDeployerError(XnodeDeployerError::new(
+    // Create count virtual machines and collect their ids, failing with PartialBatchDeploy if any did not get created
+    async fn deploy_virtual_machines(
+        &self,
+        input: &DeployInput,
+        count: u32,
+    ) -> Result<Vec<HyperstackOutput>, Error> {
+        let response = self.create_virtual_machines(input, count).await?;
+
+        let instances = match &response {
+            serde_json::Value::Object(map) => match map.get("instances") {
+                Some(serde_json::Value::Array(instances)) => instances,
+                Some(instances) => {
+                    return Err(Error::XnodeDeployerError(XnodeDeployerError::new(
                         XnodeDeployerErrorInner::HyperstackError(
                             HyperstackError::ResponseInvalidInstances {
                                 instances: instances.clone(),
                             },
                         ),
-                    ))),
-                })
-                .and_then(|instance| match instance {
-                    serde_json::Value::Object(map) => map
-                        .get("id")
-                        .ok_or(Error::XnodeDeployerError(XnodeDeployerError::new(
-                            XnodeDeployerErrorInner::HyperstackError(
-                                HyperstackError::ResponseMissingId { map: map.clone() },
-                            ),
-                        )))
-                        .and_then(|id| {
-                            match id {
-                                serde_json::Value::Number(number) => number.as_u64(),
-                                _ => None,
-                            }
-                            .ok_or(Error::XnodeDeployerError(
-                                XnodeDeployerError::new(XnodeDeployerErrorInner::HyperstackError(
-                                    HyperstackError::ResponseInvalidId { id: id.clone() },
-                                )),
-                            ))
-                        }),
-                    _ => Err(Error::XnodeDeployerError(XnodeDeployerError::new(
+                    )));
+                }
+                None => {
+                    return Err(Error::XnodeDeployerError(XnodeDeployerError::new(
                         XnodeDeployerErrorInner::HyperstackError(
-                            HyperstackError::ResponseNotObject {
-                                response: response.clone(),
-                            },
+                            HyperstackError::ResponseMissingInstances { map: map.clone() },
                         ),
-                    ))),
-                }),
-            _ => Err(Error::XnodeDeployerError(XnodeDeployerError::new(
-                XnodeDeployerErrorInner::HyperstackError(HyperstackError::ResponseNotObject {
-                    response: response.clone(),
-                }),
-            ))),
-        };
-        let id = match id {
-            Ok(id) => id,
-            Err(e) => return Err(e),
+                    )));
+                }
+            },
+            _ => {
+                return Err(Error::XnodeDeployerError(XnodeDeployerError::new(
+                    XnodeDeployerErrorInner::HyperstackError(HyperstackError::ResponseNotObject {
+                        response: response.clone(),
+                    }),
+                )));
+            }
         };
 
-        let output = Self::ProviderOutput { id };
+        let mut deployed = vec![];
+        let mut failures = vec![];
+        for instance in instances {
+            let failed = matches!(
+                instance.get("status"),
+                Some(serde_json::Value::String(status)) if status.eq_ignore_ascii_case("error")
+            );
+            match instance.get("id").and_then(|id| id.as_u64()) {
+                Some(id) if !failed => deployed.push(HyperstackOutput { id }),
+                _ => failures.push(instance.clone()),
+            }
+        }
+
+        if deployed.len() < count as usize {
+            log::warn!(
+                "Hyperstack deployment partially failed: {deployed_count} of {count} instances deployed",
+                deployed_count = deployed.len()
+            );
+            return Err(Error::XnodeDeployerError(XnodeDeployerError::new(
+                XnodeDeployerErrorInner::HyperstackError(HyperstackError::PartialBatchDeploy {
+                    requested: count,
+                    deployed,
+                    failures,
+                }),
+            )));
+        }
+
+        Ok(deployed)
+    }
+}
+
+impl XnodeDeployer for HyperstackDeployer {
+    type ProviderOutput = HyperstackOutput;
+
+    async fn deploy(&self, input: DeployInput) -> Result<Self::ProviderOutput, Error> {
+        input.validate()?;
+        log::info!(
+            "Hyperstack deployment of {input:?} on {hardware:?} started",
+            hardware = self.hardware
+        );
+        let output = self
+            .deploy_virtual_machines(&input, 1)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(Error::XnodeDeployerError(XnodeDeployerError::new(
+                XnodeDeployerErrorInner::HyperstackError(
+                    HyperstackError::ResponseEmptyInstances {},
+                ),
+            )))?;
+        match &self.hardware {
+            HyperstackHardware::VirtualMachine { volume_ids, .. } => {
+                if !volume_ids.is_empty() {
+                    self.attach_volumes_when_active(&output, volume_ids.clone())
+                        .await?;
+                }
+            }
+        }
         log::info!("Hyperstack deployment succeeded: {output:?}");
         Ok(output)
     }
@@ -207,7 +356,7 @@ impl XnodeDeployer for HyperstackDeployer {
             .delete(format!(
                 "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}"
             ))
-            .header("api_key", self.api_key.clone())
+            .header("api_key", self.api_key.expose())
             .send()
             .await
             .and_then(|response| response.error_for_status())
@@ -227,7 +376,7 @@ impl XnodeDeployer for HyperstackDeployer {
             .get(format!(
                 "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}"
             ))
-            .header("api_key", self.api_key.clone())
+            .header("api_key", self.api_key.expose())
             .send()
             .await
             .and_then(|response| response.error_for_status())
@@ -248,6 +397,35 @@ impl XnodeDeployer for HyperstackDeployer {
 
         Ok(Supported(None))
     }
+
+    async fn install_log(
+        &self,
+        xnode: &Self::ProviderOutput,
+    ) -> Result<OptionalSupport<Option<String>>, Error> {
+        let console_error = match self.console_logs(xnode).await {
+            Ok(Some(logs)) => return Ok(Supported(Some(logs))),
+            Ok(None) => None,
+            Err(e) => {
+                log::warn!(
+                    "Hyperstack console logs of virtual machine {id} failed: {e:?}",
+                    id = xnode.id
+                );
+                Some(e)
+            }
+        };
+
+        // Console logs not available, fallback to reading the log file over SSH
+        if let Some(ssh) = &self.ssh
+            && let Supported(Some(ip)) = self.ipv4(xnode).await?
+        {
+            return ssh.install_log(ip).await.map(|log| Supported(Some(log)));
+        }
+
+        match console_error {
+            Some(e) => Err(e),
+            None => Ok(Supported(None)),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -263,9 +441,34 @@ pub enum HyperstackHardware {
         environment_name: String,
         flavor_name: String,
         key_name: String,
+        #[serde(default = "default_image_name")]
+        image_name: String,
+        #[serde(default = "default_security_rules")]
+        security_rules: Vec<HyperstackSecurityRule>,
+        /// Import as keypair key_name (or reuse an existing keypair with this key) before creating the virtual machine
+        #[serde(default)]
+        public_key: Option<String>,
+        /// Volumes to attach once the virtual machine is active (deploy_batch rejects these)
+        /// If attaching fails, deploy returns VolumeAttachFailed containing the created virtual machine
+        #[serde(default)]
+        volume_ids: Vec<u64>,
+        #[serde(default = "default_assign_floating_ip")]
+        assign_floating_ip: bool,
+        /// Create the virtual machine from an image of this snapshot instead of image_name
+        /// The image must be in the region of environment_name, if it does not exist yet deploy creates it and waits up to 10 minutes for it to become ready
+        #[serde(default)]
+        snapshot_id: Option<u64>,
     },
 }
 
+fn default_image_name() -> String {
+    "Ubuntu Server 22.04 LTS (Jammy Jellyfish)".to_string()
+}
+
+fn default_assign_floating_ip() -> bool {
+    true
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum HyperstackUndeployInput {
     VirtualMachine { id: u64 },
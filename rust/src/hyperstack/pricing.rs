@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error,
+    hyperstack::{HyperstackDeployer, HyperstackOutput},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HyperstackPrice {
+    pub flavor_name: String,
+    /// USD per hour
+    pub hourly_price: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HyperstackUsage {
+    pub id: u64,
+    /// USD per hour
+    pub hourly_price: Option<f64>,
+    /// USD accumulated so far
+    pub total_cost: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct PricebookEntry {
+    name: String,
+    value: String,
+}
+
+impl HyperstackDeployer {
+    /// Hourly price of a flavor in a region (for example to show before deploying), None if it is not in the pricebook
+    pub async fn flavor_price(
+        &self,
+        flavor_name: &str,
+        region: String,
+    ) -> Result<Option<HyperstackPrice>, Error> {
+        let flavor = match self
+            .flavors(Some(region.clone()))
+            .await?
+            .into_iter()
+            .find(|flavor| flavor.name == flavor_name && flavor.region_name == region)
+        {
+            Some(flavor) => flavor,
+            None => return Ok(None),
+        };
+
+        let pricebook = self
+            .client
+            .get("https://infrahub-api.nexgencloud.com/v1/pricebook")
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<Vec<PricebookEntry>>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        // GPU flavors are priced per GPU model, others are looked up by flavor name
+        // The pricebook is not documented to contain CPU only flavors, in which case this returns None
+        let hourly_price = if !flavor.gpu.is_empty() && flavor.gpu_count > 0 {
+            pricebook
+                .iter()
+                .find(|entry| entry.name == flavor.gpu)
+                .and_then(|entry| entry.value.parse::<f64>().ok())
+                .map(|price| price * f64::from(flavor.gpu_count))
+        } else {
+            pricebook
+                .iter()
+                .find(|entry| entry.name == flavor.name)
+                .and_then(|entry| entry.value.parse::<f64>().ok())
+        };
+
+        Ok(hourly_price.map(|hourly_price| HyperstackPrice {
+            flavor_name: flavor.name,
+            hourly_price,
+        }))
+    }
+
+    /// Hourly price and accumulated cost of a deployed virtual machine, None if it has no usage yet
+    pub async fn usage(&self, xnode: &HyperstackOutput) -> Result<Option<HyperstackUsage>, Error> {
+        let id = xnode.id;
+        let response = self
+            .client
+            .get("https://infrahub-api.nexgencloud.com/v1/billing/billing/usage")
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<serde_json::Value>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        if let Some(serde_json::Value::Array(instances)) =
+            response.get("data").and_then(|data| data.get("instances"))
+            && let Some(instance) = instances
+                .iter()
+                .find(|instance| instance.get("id").and_then(|id| id.as_u64()) == Some(id))
+        {
+            return Ok(Some(HyperstackUsage {
+                id,
+                hourly_price: instance
+                    .get("hourly_rate")
+                    .and_then(|hourly_rate| hourly_rate.as_f64()),
+                total_cost: instance
+                    .get("total_cost")
+                    .and_then(|total_cost| total_cost.as_f64()),
+            }));
+        }
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,201 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error,
+    hyperstack::{HyperstackDeployer, HyperstackOutput},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HyperstackDirection {
+    Ingress,
+    Egress,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HyperstackProtocol {
+    Tcp,
+    Udp,
+    Icmp,
+    Any,
+    /// Protocol not known to this crate (only when reading back rules)
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum HyperstackEthertype {
+    IPv4,
+    IPv6,
+}
+
+// https://docs.hyperstack.cloud/docs/api-reference/core-resources/virtual-machines/vm-network/add-firewall-rule-to-vm
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HyperstackSecurityRule {
+    pub direction: HyperstackDirection,
+    pub protocol: HyperstackProtocol,
+    pub ethertype: HyperstackEthertype,
+    pub remote_ip_prefix: String,
+    pub port_range_min: Option<u16>,
+    pub port_range_max: Option<u16>,
+}
+
+impl HyperstackSecurityRule {
+    /// Allow incoming ipv4 tcp traffic from anywhere on a single port
+    pub fn ingress_tcp(port: u16) -> Self {
+        Self {
+            direction: HyperstackDirection::Ingress,
+            protocol: HyperstackProtocol::Tcp,
+            ethertype: HyperstackEthertype::IPv4,
+            remote_ip_prefix: "0.0.0.0/0".to_string(),
+            port_range_min: Some(port),
+            port_range_max: Some(port),
+        }
+    }
+}
+
+/// SSH, HTTP and HTTPS
+pub(super) fn default_security_rules() -> Vec<HyperstackSecurityRule> {
+    [22, 80, 443]
+        .into_iter()
+        .map(HyperstackSecurityRule::ingress_tcp)
+        .collect()
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HyperstackAppliedSecurityRule {
+    pub id: u64,
+    pub direction: HyperstackDirection,
+    pub protocol: HyperstackProtocol,
+    pub ethertype: HyperstackEthertype,
+    pub remote_ip_prefix: Option<String>,
+    pub port_range_min: Option<u16>,
+    pub port_range_max: Option<u16>,
+}
+
+#[derive(Deserialize)]
+struct SecurityRuleResponse {
+    security_rule: HyperstackAppliedSecurityRule,
+}
+
+#[derive(Deserialize)]
+struct InstanceResponse {
+    instance: InstanceSecurityRules,
+}
+
+#[derive(Deserialize)]
+struct InstanceSecurityRules {
+    #[serde(default)]
+    security_rules: Vec<HyperstackAppliedSecurityRule>,
+}
+
+impl HyperstackDeployer {
+    /// Security rules currently applied to a deployed virtual machine
+    pub async fn security_rules(
+        &self,
+        xnode: &HyperstackOutput,
+    ) -> Result<Vec<HyperstackAppliedSecurityRule>, Error> {
+        let id = xnode.id;
+        let response = self
+            .client
+            .get(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}"
+            ))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<InstanceResponse>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        Ok(response.instance.security_rules)
+    }
+
+    pub async fn add_security_rule(
+        &self,
+        xnode: &HyperstackOutput,
+        rule: HyperstackSecurityRule,
+    ) -> Result<HyperstackAppliedSecurityRule, Error> {
+        let id = xnode.id;
+        log::info!("Hyperstack adding security rule {rule:?} to virtual machine {id} started");
+        let response = self
+            .client
+            .post(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}/sg-rules"
+            ))
+            .json(&rule)
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<SecurityRuleResponse>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        log::info!(
+            "Hyperstack adding security rule succeeded: {rule:?}",
+            rule = response.security_rule
+        );
+        Ok(response.security_rule)
+    }
+
+    pub async fn remove_security_rule(
+        &self,
+        xnode: &HyperstackOutput,
+        rule_id: u64,
+    ) -> Result<(), Error> {
+        let id = xnode.id;
+        log::info!("Hyperstack removing security rule {rule_id} from virtual machine {id} started");
+        self.client
+            .delete(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}/sg-rules/{rule_id}"
+            ))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?;
+
+        log::info!(
+            "Hyperstack removing security rule {rule_id} from virtual machine {id} succeeded"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn read_back_unknown_protocol_and_null_prefix() {
+        let rules = serde_json::from_value::<Vec<HyperstackAppliedSecurityRule>>(json!([
+            {
+                "id": 1,
+                "direction": "egress",
+                "protocol": "any",
+                "ethertype": "IPv4",
+                "remote_ip_prefix": null,
+                "port_range_min": null,
+                "port_range_max": null
+            },
+            {
+                "id": 2,
+                "direction": "ingress",
+                "protocol": "gre",
+                "ethertype": "IPv6",
+                "remote_ip_prefix": "::/0"
+            }
+        ]))
+        .unwrap();
+        assert_eq!(rules[0].protocol, HyperstackProtocol::Any);
+        assert_eq!(rules[0].remote_ip_prefix, None);
+        assert_eq!(rules[1].protocol, HyperstackProtocol::Other);
+    }
+}
@@ -0,0 +1,192 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    Error, XnodeDeployerError,
+    hyperstack::{HyperstackDeployer, HyperstackError, HyperstackImage, HyperstackOutput},
+    utils::XnodeDeployerErrorInner,
+};
+
+// Images are created asynchronously, how long to wait for a new image to become ready before deploying from it
+const IMAGE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+const IMAGE_POLL_ATTEMPTS: u32 = 60;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HyperstackSnapshot {
+    pub id: u64,
+    pub name: String,
+    pub description: Option<String>,
+    pub status: Option<String>,
+    pub vm_id: Option<u64>,
+    /// Size in GB
+    pub size: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct SnapshotsResponse {
+    data: Vec<HyperstackSnapshot>,
+}
+
+#[derive(Deserialize)]
+struct CreateSnapshotResponse {
+    snapshot: HyperstackSnapshot,
+}
+
+#[derive(Deserialize)]
+struct CreateImageResponse {
+    image: HyperstackImage,
+}
+
+impl HyperstackDeployer {
+    /// Snapshot a deployed virtual machine, for example to deploy pre-baked Xnodes with snapshot_id
+    pub async fn create_snapshot(
+        &self,
+        xnode: &HyperstackOutput,
+        name: String,
+        description: String,
+    ) -> Result<HyperstackSnapshot, Error> {
+        let id = xnode.id;
+        log::info!("Hyperstack snapshot {name} of virtual machine {id} started");
+        let response = self
+            .client
+            .post(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}/snapshots"
+            ))
+            .json(&json!({
+                "name": name,
+                "description": description,
+                "is_image": false
+            }))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<CreateSnapshotResponse>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        log::info!(
+            "Hyperstack snapshot succeeded: {snapshot:?}",
+            snapshot = response.snapshot
+        );
+        Ok(response.snapshot)
+    }
+
+    pub async fn snapshots(&self) -> Result<Vec<HyperstackSnapshot>, Error> {
+        let response = self
+            .client
+            .get("https://infrahub-api.nexgencloud.com/v1/core/snapshots")
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<SnapshotsResponse>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        Ok(response.data)
+    }
+
+    /// Create a custom image from a snapshot, usable as image_name
+    pub async fn create_image_from_snapshot(
+        &self,
+        snapshot_id: u64,
+        name: String,
+    ) -> Result<HyperstackImage, Error> {
+        log::info!("Hyperstack creating image {name} from snapshot {snapshot_id} started");
+        let response = self
+            .client
+            .post(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/snapshots/{snapshot_id}/image"
+            ))
+            .json(&json!({
+                "name": name
+            }))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<CreateImageResponse>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        log::info!(
+            "Hyperstack creating image from snapshot {snapshot_id} succeeded: {image:?}",
+            image = response.image
+        );
+        Ok(response.image)
+    }
+
+    // Name of the custom image of a snapshot in the region of environment_name, creating it and waiting for it to be ready if needed
+    pub(super) async fn ensure_snapshot_image(
+        &self,
+        snapshot_id: u64,
+        environment_name: &str,
+    ) -> Result<String, Error> {
+        let region = self
+            .environments()
+            .await?
+            .into_iter()
+            .find(|environment| environment.name == environment_name)
+            .map(|environment| environment.region)
+            .ok_or(Error::XnodeDeployerError(XnodeDeployerError::new(
+                XnodeDeployerErrorInner::HyperstackError(HyperstackError::EnvironmentNotFound {
+                    name: environment_name.to_string(),
+                }),
+            )))?;
+
+        let name = format!("xnode-snapshot-{snapshot_id}");
+        let image = match self
+            .images(Some(region.clone()))
+            .await?
+            .into_iter()
+            .find(|image| image.name == name && image.region_name == region)
+        {
+            Some(image) => image,
+            None => {
+                self.create_image_from_snapshot(snapshot_id, name.clone())
+                    .await?
+            }
+        };
+        if image.region_name != region {
+            return Err(Error::XnodeDeployerError(XnodeDeployerError::new(
+                XnodeDeployerErrorInner::HyperstackError(HyperstackError::ImageRegionMismatch {
+                    name,
+                    image_region: image.region_name,
+                    region,
+                }),
+            )));
+        }
+
+        let mut status = image.status;
+        for _ in 0..IMAGE_POLL_ATTEMPTS {
+            match status.as_deref() {
+                Some(ready) if is_image_ready(ready) => return Ok(name),
+                Some(failed) if failed.eq_ignore_ascii_case("error") => break,
+                _ => tokio::time::sleep(IMAGE_POLL_INTERVAL).await,
+            }
+            status = self
+                .images(Some(region.clone()))
+                .await?
+                .into_iter()
+                .find(|listed| listed.id == image.id)
+                .and_then(|listed| listed.status);
+        }
+
+        Err(Error::XnodeDeployerError(XnodeDeployerError::new(
+            XnodeDeployerErrorInner::HyperstackError(HyperstackError::ImageNotReady {
+                name,
+                status,
+            }),
+        )))
+    }
+}
+
+fn is_image_ready(status: &str) -> bool {
+    status.eq_ignore_ascii_case("active") || status.eq_ignore_ascii_case("success")
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error, XnodeDeployerError,
+    hyperstack::{HyperstackDeployer, HyperstackError, HyperstackOutput},
+    utils::XnodeDeployerErrorInner,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum HyperstackStatus {
+    Creating,
+    Active,
+    Shutoff,
+    Hibernating,
+    Hibernated,
+    Restoring,
+    Error,
+    Other(String),
+}
+
+impl From<&str> for HyperstackStatus {
+    fn from(status: &str) -> Self {
+        match status.to_uppercase().as_str() {
+            "CREATING" | "BUILD" => HyperstackStatus::Creating,
+            "ACTIVE" => HyperstackStatus::Active,
+            "SHUTOFF" => HyperstackStatus::Shutoff,
+            "HIBERNATING" => HyperstackStatus::Hibernating,
+            "HIBERNATED" => HyperstackStatus::Hibernated,
+            "RESTORING" | "HIBERNATION_RESTORING" => HyperstackStatus::Restoring,
+            "ERROR" => HyperstackStatus::Error,
+            _ => HyperstackStatus::Other(status.to_string()),
+        }
+    }
+}
+
+impl HyperstackDeployer {
+    /// Current status of a deployed virtual machine
+    pub async fn status(&self, xnode: &HyperstackOutput) -> Result<HyperstackStatus, Error> {
+        let id = xnode.id;
+        let response = self
+            .client
+            .get(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}"
+            ))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<serde_json::Value>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        if let Some(serde_json::Value::Object(instance)) = response.get("instance")
+            && let Some(serde_json::Value::String(status)) = instance.get("status")
+        {
+            return Ok(HyperstackStatus::from(status.as_str()));
+        }
+
+        Err(Error::XnodeDeployerError(XnodeDeployerError::new(
+            XnodeDeployerErrorInner::HyperstackError(HyperstackError::ResponseMissingStatus {
+                response,
+            }),
+        )))
+    }
+}
@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    Error, XnodeDeployerError,
+    hyperstack::{HyperstackDeployer, HyperstackError, HyperstackOutput, HyperstackStatus},
+    utils::XnodeDeployerErrorInner,
+};
+
+// Volumes can only be attached to active virtual machines, how long to wait for a new virtual machine to become active
+const ACTIVE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+const ACTIVE_POLL_ATTEMPTS: u32 = 60;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HyperstackVolume {
+    pub id: u64,
+    pub name: String,
+    pub environment_name: String,
+    /// Size in GB
+    pub size: u64,
+    pub volume_type: String,
+    pub status: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct VolumesResponse {
+    volumes: Vec<VolumeResponse>,
+}
+
+#[derive(Deserialize)]
+struct CreateVolumeResponse {
+    volume: VolumeResponse,
+}
+
+#[derive(Deserialize)]
+struct VolumeResponse {
+    id: u64,
+    name: String,
+    environment: VolumeEnvironment,
+    size: u64,
+    volume_type: String,
+    status: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct VolumeEnvironment {
+    name: String,
+}
+
+impl From<VolumeResponse> for HyperstackVolume {
+    fn from(volume: VolumeResponse) -> Self {
+        Self {
+            id: volume.id,
+            name: volume.name,
+            environment_name: volume.environment.name,
+            size: volume.size,
+            volume_type: volume.volume_type,
+            status: volume.status,
+        }
+    }
+}
+
+impl HyperstackDeployer {
+    /// Create a persistent volume (size in GB) in an environment, volume_type for example Cloud-SSD
+    pub async fn create_volume(
+        &self,
+        name: String,
+        environment_name: String,
+        size: u64,
+        volume_type: String,
+    ) -> Result<HyperstackVolume, Error> {
+        log::info!("Hyperstack creating volume {name} in {environment_name} started");
+        let response = self
+            .client
+            .post("https://infrahub-api.nexgencloud.com/v1/core/volumes")
+            .json(&json!({
+                "name": name,
+                "environment_name": environment_name,
+                "size": size,
+                "volume_type": volume_type
+            }))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<CreateVolumeResponse>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        let volume = HyperstackVolume::from(response.volume);
+        log::info!("Hyperstack creating volume succeeded: {volume:?}");
+        Ok(volume)
+    }
+
+    pub async fn volumes(&self) -> Result<Vec<HyperstackVolume>, Error> {
+        let response = self
+            .client
+            .get("https://infrahub-api.nexgencloud.com/v1/core/volumes")
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?
+            .json::<VolumesResponse>()
+            .await
+            .map_err(Error::ReqwestError)?;
+
+        Ok(response
+            .volumes
+            .into_iter()
+            .map(HyperstackVolume::from)
+            .collect())
+    }
+
+    pub async fn delete_volume(&self, volume_id: u64) -> Result<(), Error> {
+        log::info!("Hyperstack deleting volume {volume_id} started");
+        self.client
+            .delete(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/volumes/{volume_id}"
+            ))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?;
+
+        log::info!("Hyperstack deleting volume {volume_id} succeeded");
+        Ok(())
+    }
+
+    pub async fn attach_volumes(
+        &self,
+        xnode: &HyperstackOutput,
+        volume_ids: Vec<u64>,
+    ) -> Result<(), Error> {
+        let id = xnode.id;
+        log::info!("Hyperstack attaching volumes {volume_ids:?} to virtual machine {id} started");
+        self.client
+            .post(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}/attach-volumes"
+            ))
+            .json(&json!({
+                "volume_ids": volume_ids
+            }))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?;
+
+        log::info!("Hyperstack attaching volumes to virtual machine {id} succeeded");
+        Ok(())
+    }
+
+    pub async fn detach_volumes(
+        &self,
+        xnode: &HyperstackOutput,
+        volume_ids: Vec<u64>,
+    ) -> Result<(), Error> {
+        let id = xnode.id;
+        log::info!("Hyperstack detaching volumes {volume_ids:?} from virtual machine {id} started");
+        self.client
+            .post(format!(
+                "https://infrahub-api.nexgencloud.com/v1/core/virtual-machines/{id}/detach-volumes"
+            ))
+            .json(&json!({
+                "volume_ids": volume_ids
+            }))
+            .header("api_key", self.api_key.expose())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(Error::ReqwestError)?;
+
+        log::info!("Hyperstack detaching volumes from virtual machine {id} succeeded");
+        Ok(())
+    }
+
+    // Wait for a newly created virtual machine to become active before attaching volumes
+    // Any failure is returned as VolumeAttachFailed, which contains the virtual machine to undeploy or keep
+    pub(super) async fn attach_volumes_when_active(
+        &self,
+        xnode: &HyperstackOutput,
+        volume_ids: Vec<u64>,
+    ) -> Result<(), Error> {
+        let error = match self.wait_active(xnode).await {
+            Ok(true) => match self.attach_volumes(xnode, volume_ids.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(e) => Some(Box::new(e)),
+            },
+            Ok(false) => None,
+            Err(e) => Some(Box::new(e)),
+        };
+
+        Err(Error::XnodeDeployerError(XnodeDeployerError::new(
+            XnodeDeployerErrorInner::HyperstackError(HyperstackError::VolumeAttachFailed {
+                xnode: xnode.clone(),
+                volume_ids,
+                error,
+            }),
+        )))
+    }
+
+    // Whether the virtual machine became active (false on error status or timeout)
+    async fn wait_active(&self, xnode: &HyperstackOutput) -> Result<bool, Error> {
+        for _ in 0..ACTIVE_POLL_ATTEMPTS {
+            match self.status(xnode).await? {
+                HyperstackStatus::Active => return Ok(true),
+                HyperstackStatus::Error => return Ok(false),
+                _ => tokio::time::sleep(ACTIVE_POLL_INTERVAL).await,
+            }
+        }
+
+        Ok(false)
+    }
+}
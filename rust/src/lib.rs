@@ -1,25 +1,50 @@
-use std::net::Ipv4Addr;
+use std::{fmt::Debug, net::Ipv4Addr};
 
 use serde::{Deserialize, Serialize};
 
 mod utils;
-pub use utils::{Error, XnodeDeployerError};
+pub use utils::{
+    DeployInputError, Error, MAX_INITIAL_CONFIG_SIZE, Secret, SshConfig, XnodeDeployerError,
+};
 
 #[cfg(feature = "hivelocity")]
 pub mod hivelocity;
 #[cfg(feature = "hyperstack")]
 pub mod hyperstack;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+/// Location the cloud-init script writes the XnodeOS install output to
+pub const INSTALL_LOG_PATH: &str = "/tmp/xnodeos.log";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct DeployInput {
     pub xnode_owner: Option<String>,
     pub domain: Option<String>,
     pub acme_email: Option<String>,
-    pub user_passwd: Option<String>,
-    pub encrypted: Option<String>,
+    pub user_passwd: Option<Secret>,
+    pub encrypted: Option<Secret>,
     pub initial_config: Option<String>,
 }
 
+impl Debug for DeployInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // initial_config can contain arbitrary user configuration, only log its size
+        f.debug_struct("DeployInput")
+            .field("xnode_owner", &self.xnode_owner)
+            .field("domain", &self.domain)
+            .field("acme_email", &self.acme_email)
+            .field("user_passwd", &self.user_passwd)
+            .field("encrypted", &self.encrypted)
+            .field(
+                "initial_config",
+                &self
+                    .initial_config
+                    .as_ref()
+                    .map(|initial_config| format!("<{} bytes>", initial_config.len())),
+            )
+            .finish()
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum OptionalSupport<T> {
     NotSupported,
@@ -46,28 +71,121 @@ pub trait XnodeDeployer: Send + Sync {
         &self,
         xnode: &Self::ProviderOutput,
     ) -> impl Future<Output = Result<OptionalSupport<Option<Ipv4Addr>>, Error>> + Send;
+
+    /// Get output of the XnodeOS install script on deployed hardware
+    fn install_log(
+        &self,
+        _xnode: &Self::ProviderOutput,
+    ) -> impl Future<Output = Result<OptionalSupport<Option<String>>, Error>> + Send {
+        async { Ok(OptionalSupport::NotSupported) }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum PowerState {
+    On,
+    Off,
+    Unknown,
+}
+
+pub trait XnodePower: XnodeDeployer {
+    /// Get power state of deployed hardware
+    fn power_state(
+        &self,
+        xnode: &Self::ProviderOutput,
+    ) -> impl Future<Output = Result<PowerState, Error>> + Send;
+
+    /// Power on deployed hardware
+    fn power_on(
+        &self,
+        xnode: &Self::ProviderOutput,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Power off deployed hardware (hard power off when supported)
+    fn power_off(
+        &self,
+        xnode: &Self::ProviderOutput,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Power cycle deployed hardware
+    fn reboot(
+        &self,
+        xnode: &Self::ProviderOutput,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
 }
 
 impl DeployInput {
     pub fn cloud_init(&self) -> String {
+        let env = self.env();
+        format!(
+            "#cloud-config\nruncmd:\n - |\n   {env} curl https://raw.githubusercontent.com/Openmesh-Network/xnodeos/main/install.sh | bash 2>&1 | tee {INSTALL_LOG_PATH}"
+        )
+    }
+
+    // Shell exports of all set fields, each followed by &&
+    fn env(&self) -> String {
         let mut env = vec![];
         for (name, content) in [
-            ("VERSION", &Some("v1.0.0".to_string())),
-            ("XNODE_OWNER", &self.xnode_owner),
-            ("DOMAIN", &self.domain),
-            ("ACME_EMAIL", &self.acme_email),
-            ("USER_PASSWD", &self.user_passwd),
-            ("ENCRYPTED", &self.encrypted),
-            ("INITIAL_CONFIG", &self.initial_config),
+            ("VERSION", Some("v1.0.0")),
+            ("XNODE_OWNER", self.xnode_owner.as_deref()),
+            ("DOMAIN", self.domain.as_deref()),
+            ("ACME_EMAIL", self.acme_email.as_deref()),
+            ("USER_PASSWD", self.user_passwd.as_ref().map(Secret::expose)),
+            ("ENCRYPTED", self.encrypted.as_ref().map(Secret::expose)),
+            ("INITIAL_CONFIG", self.initial_config.as_deref()),
         ] {
             if let Some(content) = content {
-                env.push(format!("export {name}=\"{content}\" && "));
+                // Single quoted, values (such as passwords) should be passed as is without shell expansion
+                let content = content.replace('\'', "'\\''");
+                env.push(format!("export {name}='{content}' && "));
             }
         }
 
-        let env = env.join("");
-        format!(
-            "#cloud-config\nruncmd:\n - |\n   {env} curl https://raw.githubusercontent.com/Openmesh-Network/xnodeos/main/install.sh | bash 2>&1 | tee /tmp/xnodeos.log"
-        )
+        env.join("")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use super::*;
+
+    fn input(user_passwd: &str) -> DeployInput {
+        DeployInput {
+            xnode_owner: None,
+            domain: None,
+            acme_email: None,
+            user_passwd: Some(Secret::from(user_passwd)),
+            encrypted: None,
+            initial_config: None,
+        }
+    }
+
+    #[test]
+    fn cloud_init_quotes_special_characters() {
+        let cloud_init = input(r#"a$b'c"d"#).cloud_init();
+        assert!(cloud_init.contains(r#"export USER_PASSWD='a$b'\''c"d' && "#));
+    }
+
+    #[test]
+    fn cloud_init_env_passed_as_is() {
+        for user_passwd in [
+            "plain",
+            "$HOME $(id) `id`",
+            "it's",
+            r#"say "hi""#,
+            r#"$6$salt$'mixed"\"#,
+        ] {
+            let output = Command::new("bash")
+                .arg("-c")
+                .arg(format!(
+                    r#"{env} printf '%s' "$USER_PASSWD""#,
+                    env = input(user_passwd).env()
+                ))
+                .output()
+                .unwrap();
+            assert_eq!(String::from_utf8(output.stdout).unwrap(), user_passwd);
+        }
     }
 }
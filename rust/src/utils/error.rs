@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use crate::utils::DeployInputError;
+
 #[cfg(feature = "hivelocity")]
 use crate::hivelocity::HivelocityError;
 #[cfg(feature = "hyperstack")]
@@ -9,6 +11,7 @@ use crate::hyperstack::HyperstackError;
 pub enum Error {
     XnodeDeployerError(XnodeDeployerError),
     ReqwestError(reqwest::Error),
+    IoError(std::io::Error),
 }
 
 #[derive(Debug)]
@@ -25,6 +28,11 @@ impl Display for XnodeDeployerError {
 #[derive(Debug)]
 pub enum XnodeDeployerErrorInner {
     Default,
+    InvalidDeployInput(Vec<DeployInputError>),
+    SshFailed {
+        code: Option<i32>,
+        stderr: String,
+    },
     #[cfg(feature = "hivelocity")]
     HivelocityError(HivelocityError),
     #[cfg(feature = "hyperstack")]
@@ -36,6 +44,14 @@ impl Display for XnodeDeployerErrorInner {
         f.write_str(
             match self {
                 XnodeDeployerErrorInner::Default => "".to_string(),
+                XnodeDeployerErrorInner::InvalidDeployInput(errors) => errors
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                XnodeDeployerErrorInner::SshFailed { code, stderr } => {
+                    format!("SSH failed with code {code:?}: {stderr}")
+                }
                 #[cfg(feature = "hivelocity")]
                 XnodeDeployerErrorInner::HivelocityError(e) => e.to_string(),
                 #[cfg(feature = "hyperstack")]
@@ -46,6 +62,14 @@ impl Display for XnodeDeployerErrorInner {
     }
 }
 
+impl From<Vec<DeployInputError>> for Error {
+    fn from(errors: Vec<DeployInputError>) -> Self {
+        Error::XnodeDeployerError(XnodeDeployerError::new(
+            XnodeDeployerErrorInner::InvalidDeployInput(errors),
+        ))
+    }
+}
+
 impl XnodeDeployerError {
     pub fn new(error: XnodeDeployerErrorInner) -> Self {
         Self {
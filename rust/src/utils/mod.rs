@@ -1,3 +1,9 @@
 mod error;
+mod secret;
+mod ssh;
+mod validate;
 
 pub use error::*;
+pub use secret::*;
+pub use ssh::*;
+pub use validate::*;
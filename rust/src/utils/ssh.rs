@@ -0,0 +1,54 @@
+use std::{net::Ipv4Addr, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+
+use crate::{Error, INSTALL_LOG_PATH, XnodeDeployerError, utils::XnodeDeployerErrorInner};
+
+/// SSH access to deployed hardware, used as fallback when the provider API does not support an operation
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SshConfig {
+    pub user: String,
+    pub port: u16,
+    pub private_key_path: PathBuf,
+}
+
+impl SshConfig {
+    pub fn new(private_key_path: PathBuf) -> Self {
+        Self {
+            user: "root".to_string(),
+            port: 22,
+            private_key_path,
+        }
+    }
+
+    /// Execute a command on the remote host with the system ssh client, returns stdout
+    pub async fn exec(&self, ip: Ipv4Addr, command: &str) -> Result<String, Error> {
+        let output = Command::new("ssh")
+            .arg("-i")
+            .arg(&self.private_key_path)
+            .args(["-p", &self.port.to_string()])
+            .args(["-o", "BatchMode=yes"])
+            .args(["-o", "StrictHostKeyChecking=accept-new"])
+            .arg(format!("{user}@{ip}", user = self.user))
+            .arg(command)
+            .output()
+            .await
+            .map_err(Error::IoError)?;
+
+        if !output.status.success() {
+            return Err(Error::XnodeDeployerError(XnodeDeployerError::new(
+                XnodeDeployerErrorInner::SshFailed {
+                    code: output.status.code(),
+                    stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+                },
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    pub async fn install_log(&self, ip: Ipv4Addr) -> Result<String, Error> {
+        self.exec(ip, &format!("cat {INSTALL_LOG_PATH}")).await
+    }
+}
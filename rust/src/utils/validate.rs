@@ -0,0 +1,254 @@
+use std::fmt::Display;
+
+use crate::DeployInput;
+
+/// Maximum size of initial_config, provider user data is limited in size
+pub const MAX_INITIAL_CONFIG_SIZE: usize = 16 * 1024;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeployInputError {
+    InvalidXnodeOwner { xnode_owner: String },
+    InvalidDomain { domain: String },
+    InvalidAcmeEmail { acme_email: String },
+    InitialConfigTooLarge { size: usize, max: usize },
+}
+
+impl Display for DeployInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            match self {
+                DeployInputError::InvalidXnodeOwner { xnode_owner } => {
+                    format!("Invalid xnode owner: {xnode_owner}")
+                }
+                DeployInputError::InvalidDomain { domain } => {
+                    format!("Invalid domain: {domain}")
+                }
+                DeployInputError::InvalidAcmeEmail { acme_email } => {
+                    format!("Invalid acme email: {acme_email}")
+                }
+                DeployInputError::InitialConfigTooLarge { size, max } => {
+                    format!("Initial config too large: {size} bytes (max {max})")
+                }
+            }
+            .as_str(),
+        )
+    }
+}
+
+impl DeployInput {
+    /// Check all fields before any hardware is rented, returns every invalid field
+    pub fn validate(&self) -> Result<(), Vec<DeployInputError>> {
+        let mut errors = vec![];
+
+        if let Some(xnode_owner) = &self.xnode_owner
+            && !is_valid_owner(xnode_owner)
+        {
+            errors.push(DeployInputError::InvalidXnodeOwner {
+                xnode_owner: xnode_owner.clone(),
+            });
+        }
+
+        if let Some(domain) = &self.domain
+            && !is_valid_domain(domain)
+        {
+            errors.push(DeployInputError::InvalidDomain {
+                domain: domain.clone(),
+            });
+        }
+
+        if let Some(acme_email) = &self.acme_email
+            && !is_valid_email(acme_email)
+        {
+            errors.push(DeployInputError::InvalidAcmeEmail {
+                acme_email: acme_email.clone(),
+            });
+        }
+
+        if let Some(initial_config) = &self.initial_config
+            && initial_config.len() > MAX_INITIAL_CONFIG_SIZE
+        {
+            errors.push(DeployInputError::InitialConfigTooLarge {
+                size: initial_config.len(),
+                max: MAX_INITIAL_CONFIG_SIZE,
+            });
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+// Owner in the format <type>:<id>, only the address of eth owners (40 hex characters, optional 0x prefix) is checked
+fn is_valid_owner(owner: &str) -> bool {
+    match owner.split_once(':') {
+        Some(("eth", address)) => {
+            let address = address.strip_prefix("0x").unwrap_or(address);
+            address.len() == 40 && address.chars().all(|c| c.is_ascii_hexdigit())
+        }
+        Some((owner_type, id)) => {
+            !owner_type.is_empty()
+                && owner_type.chars().all(|c| c.is_ascii_alphanumeric())
+                && !id.is_empty()
+                && !id.chars().any(|c| c.is_whitespace())
+        }
+        None => false,
+    }
+}
+
+fn is_valid_domain(domain: &str) -> bool {
+    let labels: Vec<&str> = domain.split('.').collect();
+    domain.len() <= 253
+        && labels.len() >= 2
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+fn is_valid_email(email: &str) -> bool {
+    match email.rsplit_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && local.len() <= 64
+                && local
+                    .chars()
+                    .all(|c| c.is_ascii_graphic() && !matches!(c, '@' | '"' | '\'' | '\\'))
+                && is_valid_domain(domain)
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input() -> DeployInput {
+        DeployInput {
+            xnode_owner: Some("eth:519ce4c129a981b2cbb4c3990b1391da24e8ebf3".to_string()),
+            domain: Some("xnode.example.com".to_string()),
+            acme_email: Some("admin@example.com".to_string()),
+            user_passwd: None,
+            encrypted: None,
+            initial_config: Some("{}".to_string()),
+        }
+    }
+
+    #[test]
+    fn valid_input() {
+        assert_eq!(input().validate(), Ok(()));
+    }
+
+    #[test]
+    fn empty_input() {
+        let input = DeployInput {
+            xnode_owner: None,
+            domain: None,
+            acme_email: None,
+            user_passwd: None,
+            encrypted: None,
+            initial_config: None,
+        };
+        assert_eq!(input.validate(), Ok(()));
+    }
+
+    #[test]
+    fn owner() {
+        for owner in [
+            "eth:519ce4c129a981b2cbb4c3990b1391da24e8ebf3",
+            "eth:0x519cE4C129a981B2CBB4C3990B1391dA24E8EbF3",
+            "sol:7EcDhSYGxXyscszYEp35KHN8vvw3svAuLKTzXwCFLtV",
+        ] {
+            assert!(is_valid_owner(owner), "{owner}");
+        }
+        for owner in [
+            "519ce4c129a981b2cbb4c3990b1391da24e8ebf3",
+            "eth:519ce4c129a981b2cbb4c3990b1391da24e8eb",
+            "eth:zz9ce4c129a981b2cbb4c3990b1391da24e8ebf3",
+            ":abc",
+            "sol:",
+            "sol:a b",
+        ] {
+            assert!(!is_valid_owner(owner), "{owner}");
+        }
+    }
+
+    #[test]
+    fn domain() {
+        for domain in ["example.com", "a.b-c.example.co", "xn--bcher-kva.example"] {
+            assert!(is_valid_domain(domain), "{domain}");
+        }
+        for domain in [
+            "localhost",
+            "example..com",
+            "-example.com",
+            "example-.com",
+            "exa mple.com",
+            "example.com.",
+            &format!("{}.com", "a".repeat(64)),
+        ] {
+            assert!(!is_valid_domain(domain), "{domain}");
+        }
+    }
+
+    #[test]
+    fn email() {
+        for email in ["admin@example.com", "first.last+xnode@mail.example.org"] {
+            assert!(is_valid_email(email), "{email}");
+        }
+        for email in [
+            "admin",
+            "@example.com",
+            "admin@localhost",
+            "ad min@example.com",
+            "admin@@example.com",
+            "\"admin\"@example.com",
+        ] {
+            assert!(!is_valid_email(email), "{email}");
+        }
+    }
+
+    #[test]
+    fn initial_config_size() {
+        let mut input = input();
+        input.initial_config = Some("a".repeat(MAX_INITIAL_CONFIG_SIZE));
+        assert_eq!(input.validate(), Ok(()));
+
+        input.initial_config = Some("a".repeat(MAX_INITIAL_CONFIG_SIZE + 1));
+        assert_eq!(
+            input.validate(),
+            Err(vec![DeployInputError::InitialConfigTooLarge {
+                size: MAX_INITIAL_CONFIG_SIZE + 1,
+                max: MAX_INITIAL_CONFIG_SIZE,
+            }])
+        );
+    }
+
+    #[test]
+    fn all_invalid_fields_reported() {
+        let mut input = input();
+        input.xnode_owner = Some("owner".to_string());
+        input.domain = Some("domain".to_string());
+        input.acme_email = Some("email".to_string());
+        assert_eq!(
+            input.validate(),
+            Err(vec![
+                DeployInputError::InvalidXnodeOwner {
+                    xnode_owner: "owner".to_string()
+                },
+                DeployInputError::InvalidDomain {
+                    domain: "domain".to_string()
+                },
+                DeployInputError::InvalidAcmeEmail {
+                    acme_email: "email".to_string()
+                },
+            ])
+        );
+    }
+}